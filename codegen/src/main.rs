@@ -80,8 +80,8 @@ struct IndexTemplateCfg {
 //     }
 // }
 
-const GLAM_TYPES: [&'static str; 3] = ["IVec2", "UVec2", "Vec2"];
-const SHAPES: [&'static str; 3] = ["Rectangle", "Circle", "LineSegment"];
+const GLAM_TYPES: [&str; 3] = ["IVec2", "UVec2", "Vec2"];
+const SHAPES: [&str; 13] = ["Rectangle", "Circle", "LineSegment", "Ellipse", "Polygon", "Triangle", "Polyline", "Arc", "Sector", "RoundedRectangle", "Capsule", "QuadraticBezier", "CubicBezier"];
 // [("IVec2", "i", 32), ("UVec2", "u", 32), ("Vec2", "f", 32)];
 
 fn main() -> anyhow::Result<()> {
@@ -119,7 +119,6 @@ fn main() -> anyhow::Result<()> {
                     "num_size": num_size, 
                     "num_suffix": if int_based { "" } else { ".0" },
                 })).unwrap(),
-                ..Default::default()
             })
 
 
@@ -136,7 +135,6 @@ fn main() -> anyhow::Result<()> {
                 "glam_type": glam_type,
                 "path_delim": std::path::MAIN_SEPARATOR,
             })).unwrap(),
-            ..Default::default()
         })
     }
 
@@ -193,7 +191,7 @@ fn main() -> anyhow::Result<()> {
         let dirname = output_path.parent().unwrap();
 
         if !dirname.exists() {
-            std::fs::create_dir_all(&dirname)
+            std::fs::create_dir_all(dirname)
                 .with_context(|| format!("failed to create {:?}", dirname))?;
         }
         std::fs::write(&output_path, output_str)
@@ -209,7 +207,7 @@ fn main() -> anyhow::Result<()> {
         // let dirname = output_path.parent().unwrap();
 
         // if !dirname.exists() {
-        //     std::fs::create_dir_all(&dirname)
+        //     std::fs::create_dir_all(dirname)
         //         .with_context(|| format!("failed to create {:?}", dirname))?;
         // }
         std::fs::write(&output_path, output_str)
@@ -0,0 +1,166 @@
+use std::ops::Range;
+
+use glam::IVec2;
+
+use crate::{ivec2, Shape};
+
+/// A two dimensional buffer of pixels that shapes can be drawn into.
+pub trait PixelBuffer {
+    type Pixel: Copy;
+
+    fn width(&self) -> u32;
+    fn height(&self) -> u32;
+
+    /// Returns the pixel at `point`, or `None` if it lies outside of the buffer.
+    fn get_pixel(&self, point: IVec2) -> Option<Self::Pixel>;
+
+    /// Sets the pixel at `point`, points outside of the buffer are ignored.
+    fn set_pixel(&mut self, point: IVec2, pixel: Self::Pixel);
+
+    /// Sets the pixels of row `y` in the range `columns`, pixels outside of the buffer are
+    /// ignored.
+    fn fill_span(&mut self, y: i32, columns: Range<i32>, pixel: Self::Pixel) {
+        for x in columns {
+            self.set_pixel(IVec2::new(x, y), pixel);
+        }
+    }
+
+    /// Returns the rectangle covering all pixels of the buffer.
+    fn bounding_box(&self) -> ivec2::Rectangle {
+        ivec2::Rectangle::new_on_origin(IVec2::new(self.width() as i32, self.height() as i32))
+    }
+}
+
+/// A pixel buffer backed by a mutable slice, with rows `stride` elements apart.
+#[derive(Debug)]
+pub struct SliceBuffer<'a, T> {
+    data: &'a mut [T],
+    width: u32,
+    height: u32,
+    stride: usize,
+}
+
+impl<'a, T: Copy> SliceBuffer<'a, T> {
+    /// Creates a buffer of tightly packed rows of `width` pixels.
+    pub fn new(data: &'a mut [T], width: u32) -> Self {
+        Self::new_with_stride(data, width, width as usize)
+    }
+
+    /// Creates a buffer of rows of `width` pixels that start `stride` elements apart.
+    ///
+    /// The last row doesn't need to be padded to the full stride. Panics if `stride` is smaller
+    /// than `width`.
+    pub fn new_with_stride(data: &'a mut [T], width: u32, stride: usize) -> Self {
+        assert!(stride >= width as usize, "stride {} is smaller than width {}", stride, width);
+
+        let height = if width == 0 || data.len() < width as usize {
+            0
+        } else {
+            (data.len() - width as usize) / stride + 1
+        };
+
+        Self {
+            data,
+            width,
+            height: height as u32,
+            stride,
+        }
+    }
+
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    fn index(&self, point: IVec2) -> Option<usize> {
+        if point.x < 0 || point.y < 0 || point.x as u32 >= self.width || point.y as u32 >= self.height {
+            return None;
+        }
+        Some(point.y as usize * self.stride + point.x as usize)
+    }
+}
+
+impl<T: Copy> PixelBuffer for SliceBuffer<'_, T> {
+    type Pixel = T;
+
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn get_pixel(&self, point: IVec2) -> Option<T> {
+        self.index(point).map(|index| self.data[index])
+    }
+
+    fn set_pixel(&mut self, point: IVec2, pixel: T) {
+        if let Some(index) = self.index(point) {
+            self.data[index] = pixel;
+        }
+    }
+
+    fn fill_span(&mut self, y: i32, columns: Range<i32>, pixel: T) {
+        if y < 0 || y as u32 >= self.height {
+            return;
+        }
+        let start = columns.start.max(0) as usize;
+        let end = columns.end.clamp(0, self.width as i32) as usize;
+        if start < end {
+            let row = y as usize * self.stride;
+            self.data[row + start..row + end].fill(pixel);
+        }
+    }
+}
+
+/// Fills `shape` into `buffer`, only the pixels inside the buffer are visited.
+pub fn fill_shape<B: PixelBuffer>(buffer: &mut B, shape: &impl Shape, pixel: B::Pixel) {
+    for point in shape.fill_iter_clipped(buffer.bounding_box()) {
+        buffer.set_pixel(point, pixel);
+    }
+}
+
+/// Draws the outline of `shape` into `buffer`, only the pixels inside the buffer are visited.
+pub fn stroke_shape<B: PixelBuffer>(buffer: &mut B, shape: &impl Shape, pixel: B::Pixel) {
+    for point in shape.outline_iter_clipped(buffer.bounding_box()) {
+        buffer.set_pixel(point, pixel);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stride() {
+        let mut data = [0_u32; 4 * 3 - 1];
+        let mut buffer = SliceBuffer::new_with_stride(&mut data, 3, 4);
+        assert_eq!((buffer.width(), buffer.height()), (3, 3));
+
+        buffer.set_pixel(IVec2::new(2, 1), 7);
+        buffer.set_pixel(IVec2::new(3, 1), 9);
+        buffer.fill_span(2, -1..2, 5);
+
+        assert_eq!(buffer.get_pixel(IVec2::new(2, 1)), Some(7));
+        assert_eq!(buffer.get_pixel(IVec2::new(3, 1)), None);
+        assert_eq!(data, [0, 0, 0, 0, 0, 0, 7, 0, 5, 5, 0]);
+    }
+
+    #[test]
+    fn test_fill_and_stroke_clip() {
+        let mut data = [0_u8; 16];
+        let mut buffer = SliceBuffer::new(&mut data, 4);
+        let rect = ivec2::Rectangle::new(IVec2::new(-2, 1), IVec2::new(3, 10));
+
+        fill_shape(&mut buffer, &rect, 1);
+        stroke_shape(&mut buffer, &ivec2::Rectangle::new(IVec2::new(1, -1), IVec2::new(4, 2)), 2);
+
+        #[rustfmt::skip]
+        assert_eq!(data, [
+            0, 2, 0, 2,
+            1, 2, 2, 2,
+            1, 1, 1, 0,
+            1, 1, 1, 0,
+        ]);
+    }
+}
@@ -0,0 +1,269 @@
+use glam::IVec2;
+
+use crate::buffer::PixelBuffer;
+use crate::Shape;
+
+/// An 8 bit per channel color with straight, not premultiplied, alpha.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Rgba8 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba8 {
+    pub const TRANSPARENT: Self = Self::new(0, 0, 0, 0);
+    pub const BLACK: Self = Self::new(0, 0, 0, 255);
+    pub const WHITE: Self = Self::new(255, 255, 255, 255);
+
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Creates a color from a `0xAARRGGBB` value.
+    pub const fn from_argb_u32(argb: u32) -> Self {
+        Self::new((argb >> 16) as u8, (argb >> 8) as u8, argb as u8, (argb >> 24) as u8)
+    }
+
+    /// Returns the color as a `0xAARRGGBB` value.
+    pub const fn to_argb_u32(self) -> u32 {
+        (self.a as u32) << 24 | (self.r as u32) << 16 | (self.g as u32) << 8 | self.b as u32
+    }
+}
+
+/// A color with premultiplied alpha, all channels are in `0.0..=1.0`.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct PremultipliedRgba {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl PremultipliedRgba {
+    pub const TRANSPARENT: Self = Self::new(0.0, 0.0, 0.0, 0.0);
+
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Returns the color with all channels multiplied by `factor`, used to apply coverage.
+    pub fn scale(self, factor: f32) -> Self {
+        Self::new(self.r * factor, self.g * factor, self.b * factor, self.a * factor)
+    }
+
+    fn map(self, other: Self, f: impl Fn(f32, f32) -> f32) -> Self {
+        Self::new(f(self.r, other.r), f(self.g, other.g), f(self.b, other.b), f(self.a, other.a))
+    }
+}
+
+impl From<Rgba8> for PremultipliedRgba {
+    fn from(color: Rgba8) -> Self {
+        let a = color.a as f32 / 255.0;
+        Self::new(
+            color.r as f32 / 255.0 * a,
+            color.g as f32 / 255.0 * a,
+            color.b as f32 / 255.0 * a,
+            a,
+        )
+    }
+}
+
+impl From<PremultipliedRgba> for Rgba8 {
+    fn from(color: PremultipliedRgba) -> Self {
+        let a = color.a.clamp(0.0, 1.0);
+        if a == 0.0 {
+            return Rgba8::TRANSPARENT;
+        }
+        let channel = |c: f32| ((c / a).clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        Rgba8::new(channel(color.r), channel(color.g), channel(color.b), (a * 255.0).round() as u8)
+    }
+}
+
+/// Pixel types that colors can be blended into.
+pub trait Color: Copy {
+    fn to_premultiplied(self) -> PremultipliedRgba;
+    fn from_premultiplied(color: PremultipliedRgba) -> Self;
+}
+
+impl Color for Rgba8 {
+    fn to_premultiplied(self) -> PremultipliedRgba {
+        self.into()
+    }
+
+    fn from_premultiplied(color: PremultipliedRgba) -> Self {
+        color.into()
+    }
+}
+
+impl Color for PremultipliedRgba {
+    fn to_premultiplied(self) -> PremultipliedRgba {
+        self
+    }
+
+    fn from_premultiplied(color: PremultipliedRgba) -> Self {
+        color
+    }
+}
+
+/// `0xAARRGGBB` pixels with straight alpha.
+impl Color for u32 {
+    fn to_premultiplied(self) -> PremultipliedRgba {
+        Rgba8::from_argb_u32(self).into()
+    }
+
+    fn from_premultiplied(color: PremultipliedRgba) -> Self {
+        Rgba8::from(color).to_argb_u32()
+    }
+}
+
+/// How a color is combined with the pixel it is drawn over.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum BlendMode {
+    /// Porter-Duff source over, the source is painted on top of the destination.
+    #[default]
+    SourceOver,
+    /// The colors are added, clamped to `1.0`.
+    Additive,
+    /// The colors are multiplied where both are opaque, each color shows where the other one
+    /// is transparent.
+    Multiply,
+}
+
+impl BlendMode {
+    /// Returns `source` blended over `destination`, both premultiplied.
+    pub fn blend(self, source: PremultipliedRgba, destination: PremultipliedRgba) -> PremultipliedRgba {
+        let (sa, da) = (source.a, destination.a);
+        match self {
+            BlendMode::SourceOver => source.map(destination, |s, d| s + d * (1.0 - sa)),
+            BlendMode::Additive => source.map(destination, |s, d| (s + d).min(1.0)),
+            BlendMode::Multiply => {
+                let mut color = source.map(destination, |s, d| s * d + s * (1.0 - da) + d * (1.0 - sa));
+                color.a = sa + da - sa * da;
+                color
+            }
+        }
+    }
+}
+
+/// Blends `color` into the pixel at `point`, weighted by `coverage` in `0.0..=1.0`.
+///
+/// Points outside of the buffer are ignored.
+pub fn blend_pixel<B: PixelBuffer>(
+    buffer: &mut B,
+    point: IVec2,
+    color: PremultipliedRgba,
+    coverage: f32,
+    mode: BlendMode,
+) where
+    B::Pixel: Color,
+{
+    if let Some(destination) = buffer.get_pixel(point) {
+        let source = color.scale(coverage.clamp(0.0, 1.0));
+        let blended = mode.blend(source, destination.to_premultiplied());
+        buffer.set_pixel(point, B::Pixel::from_premultiplied(blended));
+    }
+}
+
+/// Blends the filled `shape`, or its outline, into `buffer`.
+///
+/// Only the pixels inside the buffer are visited, and each pixel is blended once.
+pub fn draw_shape<B: PixelBuffer>(
+    buffer: &mut B,
+    shape: &impl Shape,
+    outline: bool,
+    color: impl Into<PremultipliedRgba>,
+    mode: BlendMode,
+) where
+    B::Pixel: Color,
+{
+    let color = color.into();
+    let clip = buffer.bounding_box();
+    if outline {
+        for point in shape.outline_iter_clipped(clip) {
+            blend_pixel(buffer, point, color, 1.0, mode);
+        }
+    } else {
+        for point in shape.fill_iter_clipped(clip) {
+            blend_pixel(buffer, point, color, 1.0, mode);
+        }
+    }
+}
+
+/// Blends pixels with their coverage, like the output of an anti-aliased line, into `buffer`.
+pub fn draw_coverage<B: PixelBuffer>(
+    buffer: &mut B,
+    pixels: impl IntoIterator<Item = (IVec2, f32)>,
+    color: impl Into<PremultipliedRgba>,
+    mode: BlendMode,
+) where
+    B::Pixel: Color,
+{
+    let color = color.into();
+    for (point, coverage) in pixels {
+        blend_pixel(buffer, point, color, coverage, mode);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::buffer::SliceBuffer;
+    use crate::ivec2;
+
+    #[test]
+    fn test_conversions() {
+        let color = Rgba8::new(255, 128, 0, 128);
+        assert_eq!(Rgba8::from_argb_u32(color.to_argb_u32()), color);
+        assert_eq!(color.to_argb_u32(), 0x80ff8000);
+        assert_eq!(Rgba8::from(PremultipliedRgba::from(color)), color);
+
+        let premultiplied = PremultipliedRgba::from(color);
+        assert_abs_diff_eq!(premultiplied.r, premultiplied.a);
+    }
+
+    #[test]
+    fn test_blend_modes() {
+        let red = PremultipliedRgba::new(0.5, 0.0, 0.0, 0.5);
+        let blue = PremultipliedRgba::new(0.0, 0.0, 1.0, 1.0);
+
+        assert_eq!(BlendMode::SourceOver.blend(red, blue), PremultipliedRgba::new(0.5, 0.0, 0.5, 1.0));
+        assert_eq!(BlendMode::Additive.blend(red, blue), PremultipliedRgba::new(0.5, 0.0, 1.0, 1.0));
+        assert_eq!(BlendMode::Multiply.blend(red, blue), PremultipliedRgba::new(0.0, 0.0, 0.5, 1.0));
+
+        let white = PremultipliedRgba::new(1.0, 1.0, 1.0, 1.0);
+        assert_eq!(BlendMode::Multiply.blend(white, blue), blue);
+        assert_eq!(BlendMode::SourceOver.blend(red, PremultipliedRgba::TRANSPARENT), red);
+    }
+
+    #[test]
+    fn test_draw_translucent_shape() {
+        let mut data = [0xff000000_u32; 9];
+        let mut buffer = SliceBuffer::new(&mut data, 3);
+        let rect = ivec2::Rectangle::new(IVec2::new(-1, -1), IVec2::new(1, 1));
+
+        draw_shape(&mut buffer, &rect, false, Rgba8::new(255, 255, 255, 128), BlendMode::SourceOver);
+        draw_shape(&mut buffer, &rect, false, Rgba8::new(255, 255, 255, 128), BlendMode::SourceOver);
+
+        assert_eq!(data[0], 0xffc0c0c0);
+        assert_eq!(data[1], 0xff000000);
+        assert_eq!(data[3], 0xff000000);
+    }
+
+    #[test]
+    fn test_draw_coverage() {
+        let mut data = [Rgba8::BLACK; 4];
+        let mut buffer = SliceBuffer::new(&mut data, 2);
+        let pixels = [(IVec2::new(0, 0), 1.0), (IVec2::new(1, 0), 0.5), (IVec2::new(5, 5), 1.0)];
+
+        draw_coverage(&mut buffer, pixels, Rgba8::WHITE, BlendMode::SourceOver);
+
+        assert_eq!(data[0], Rgba8::WHITE);
+        assert_eq!(data[1], Rgba8::new(128, 128, 128, 255));
+        assert_eq!(data[2], Rgba8::BLACK);
+    }
+}
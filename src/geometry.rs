@@ -0,0 +1,126 @@
+use glam::Vec2;
+
+use crate::vec2;
+
+/// Where two line segments meet.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SegmentIntersection {
+    /// The segments cross or touch in a single point.
+    Point(Vec2),
+    /// The segments are collinear and share this part, which runs in the direction of the first
+    /// segment.
+    Overlap(vec2::LineSegment),
+}
+
+/// Returns the point of the segment from `start` to `end` that is closest to `point`.
+pub(crate) fn closest_point(start: Vec2, end: Vec2, point: Vec2) -> Vec2 {
+    let delta = end - start;
+    let length_squared = delta.length_squared();
+    if length_squared == 0.0 {
+        return start;
+    }
+    let t = ((point - start).dot(delta) / length_squared).clamp(0.0, 1.0);
+    start + delta * t
+}
+
+/// Returns `true` if `value` is zero, relative to the size of the vectors it was computed from.
+fn is_zero(value: f32, scale: f32) -> bool {
+    value.abs() <= f32::EPSILON * 4.0 * scale.max(1.0)
+}
+
+/// Returns where the segment from `a_start` to `a_end` meets the one from `b_start` to `b_end`.
+pub(crate) fn intersection(a_start: Vec2, a_end: Vec2, b_start: Vec2, b_end: Vec2) -> Option<SegmentIntersection> {
+    let (a, b) = (a_end - a_start, b_end - b_start);
+    let offset = b_start - a_start;
+    let scale = a.length_squared().max(b.length_squared()).max(offset.length_squared());
+
+    let denominator = a.perp_dot(b);
+    if !is_zero(denominator, scale) {
+        let t = offset.perp_dot(b) / denominator;
+        let u = offset.perp_dot(a) / denominator;
+        if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+            return Some(SegmentIntersection::Point(a_start + a * t));
+        }
+        return None;
+    }
+
+    // parallel, the segments only meet if they lie on the same line
+    if !is_zero(offset.perp_dot(a), scale) || !is_zero(offset.perp_dot(b), scale) {
+        return None;
+    }
+    if a.length_squared() == 0.0 {
+        let on_b = closest_point(b_start, b_end, a_start) == a_start;
+        return on_b.then_some(SegmentIntersection::Point(a_start));
+    }
+
+    // positions of the end points of `b` along `a`
+    let length_squared = a.length_squared();
+    let t0 = offset.dot(a) / length_squared;
+    let t1 = (b_end - a_start).dot(a) / length_squared;
+    let (first, last) = (t0.min(t1).max(0.0), t0.max(t1).min(1.0));
+    if first > last {
+        return None;
+    }
+
+    let (start, end) = (a_start + a * first, a_start + a * last);
+    if start == end {
+        Some(SegmentIntersection::Point(start))
+    } else {
+        Some(SegmentIntersection::Overlap(vec2::LineSegment::new(start, end)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_closest_point() {
+        let (start, end) = (Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0));
+
+        assert_eq!(closest_point(start, end, Vec2::new(4.0, 3.0)), Vec2::new(4.0, 0.0));
+        assert_eq!(closest_point(start, end, Vec2::new(-4.0, 3.0)), start);
+        assert_eq!(closest_point(start, end, Vec2::new(14.0, -3.0)), end);
+        assert_eq!(closest_point(start, start, Vec2::new(4.0, 3.0)), start);
+    }
+
+    #[test]
+    fn test_intersection() {
+        let v = Vec2::new;
+
+        assert_eq!(
+            intersection(v(0.0, 0.0), v(4.0, 4.0), v(0.0, 4.0), v(4.0, 0.0)),
+            Some(SegmentIntersection::Point(v(2.0, 2.0)))
+        );
+        // touching at an end point
+        assert_eq!(
+            intersection(v(0.0, 0.0), v(4.0, 0.0), v(4.0, 0.0), v(4.0, 3.0)),
+            Some(SegmentIntersection::Point(v(4.0, 0.0)))
+        );
+        assert_eq!(intersection(v(0.0, 0.0), v(4.0, 4.0), v(0.0, 4.0), v(1.0, 3.0)), None);
+        // parallel
+        assert_eq!(intersection(v(0.0, 0.0), v(4.0, 0.0), v(0.0, 1.0), v(4.0, 1.0)), None);
+    }
+
+    #[test]
+    fn test_collinear() {
+        let v = Vec2::new;
+
+        assert_eq!(
+            intersection(v(0.0, 0.0), v(6.0, 0.0), v(8.0, 0.0), v(4.0, 0.0)),
+            Some(SegmentIntersection::Overlap(vec2::LineSegment::new(v(4.0, 0.0), v(6.0, 0.0))))
+        );
+        assert_eq!(
+            intersection(v(0.0, 0.0), v(3.0, 3.0), v(3.0, 3.0), v(5.0, 5.0)),
+            Some(SegmentIntersection::Point(v(3.0, 3.0)))
+        );
+        assert_eq!(intersection(v(0.0, 0.0), v(2.0, 0.0), v(3.0, 0.0), v(5.0, 0.0)), None);
+
+        // a segment of zero length is a point
+        assert_eq!(
+            intersection(v(2.0, 1.0), v(2.0, 1.0), v(0.0, 0.0), v(4.0, 2.0)),
+            Some(SegmentIntersection::Point(v(2.0, 1.0)))
+        );
+        assert_eq!(intersection(v(2.0, 2.0), v(2.0, 2.0), v(0.0, 0.0), v(4.0, 2.0)), None);
+    }
+}
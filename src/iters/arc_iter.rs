@@ -0,0 +1,365 @@
+use std::f32::consts::TAU;
+
+use glam::{IVec2, Vec2};
+
+use super::circle_iter::CirclePoints;
+use super::circle_perimeter_iter::{CirclePerimeter, Direction};
+use super::ring_iter::RingPixels;
+use super::sdf_iter::segment_distance;
+use crate::ivec2;
+
+/// Range of angles swept clockwise on screen from a start angle to an end angle.
+///
+/// Angles are in radians with `0.0` along the positive x axis, the same as for
+/// `CirclePerimeter`. The range is half open, so sectors that share an angle never overlap. An end
+/// angle before the start angle wraps around, a range of a full turn or more covers everything.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct AngleRange {
+    start: f32,
+    sweep: f32,
+}
+
+impl AngleRange {
+    pub(crate) fn new(start_angle: f32, end_angle: f32) -> Self {
+        let sweep = end_angle - start_angle;
+
+        Self {
+            start: start_angle.rem_euclid(TAU),
+            sweep: if sweep >= TAU { TAU } else { sweep.rem_euclid(TAU) },
+        }
+    }
+
+    /// Returns `true` if the direction of `offset` lies inside the range.
+    fn contains(&self, offset: IVec2) -> bool {
+        self.contains_direction(offset.as_vec2())
+    }
+
+    fn contains_direction(&self, offset: Vec2) -> bool {
+        if self.sweep >= TAU {
+            return true;
+        }
+        let angle = offset.y.atan2(offset.x);
+
+        (angle - self.start).rem_euclid(TAU) < self.sweep
+    }
+
+    /// Returns the unit vectors along the start and the end angle.
+    fn end_directions(&self) -> [Vec2; 2] {
+        [Vec2::from_angle(self.start), Vec2::from_angle(self.start + self.sweep)]
+    }
+}
+
+/// Pixel tests of a circle restricted to a range of angles around its center.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Slice {
+    center_2x: IVec2,
+    threshold: i32,
+    range: AngleRange,
+}
+
+impl Slice {
+    fn new(circle: &ivec2::Circle, range: AngleRange) -> Self {
+        Self {
+            center_2x: circle.center_2x(),
+            threshold: circle.threshold(),
+            range,
+        }
+    }
+
+    /// Offset of the center of pixel `point` from the center of the circle, in doubled
+    /// coordinates. Both components are odd, so the offset is never zero.
+    fn offset(&self, point: IVec2) -> IVec2 {
+        point * 2 - self.center_2x
+    }
+
+    fn in_circle(&self, point: IVec2) -> bool {
+        self.offset(point).length_squared() < self.threshold
+    }
+
+    fn in_range(&self, point: IVec2) -> bool {
+        self.range.contains(self.offset(point))
+    }
+
+    fn on_perimeter(&self, point: IVec2) -> bool {
+        self.in_circle(point) && neighbours(point).iter().any(|n| !self.in_circle(*n))
+    }
+
+    fn in_sector(&self, point: IVec2) -> bool {
+        self.in_circle(point) && self.in_range(point)
+    }
+}
+
+fn neighbours(point: IVec2) -> [IVec2; 4] {
+    [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y].map(|d| point + d)
+}
+
+/// Returns `true` if the pixel at `point` is part of the arc drawn by `ArcPixels`.
+pub(crate) fn arc_contains(circle: &ivec2::Circle, range: AngleRange, point: IVec2) -> bool {
+    let slice = Slice::new(circle, range);
+    slice.on_perimeter(point) && slice.in_range(point)
+}
+
+/// Returns `true` if the pixel at `point` is filled by the sector.
+pub(crate) fn sector_contains(circle: &ivec2::Circle, range: AngleRange, point: IVec2) -> bool {
+    Slice::new(circle, range).in_sector(point)
+}
+
+/// Returns the signed distance from `point` to the sector of the circle around `center` with
+/// `radius` between the angles of `range`.
+///
+/// Inside the range of angles the sector is the circle cut by the two straight edges, outside of
+/// it the closest point is on one of the edges.
+pub(crate) fn sector_distance(center: Vec2, radius: f32, range: AngleRange, point: Vec2) -> f32 {
+    let offset = point - center;
+    let circle_distance = offset.length() - radius;
+    if range.sweep >= TAU {
+        return circle_distance;
+    }
+
+    let edge_distance = range
+        .end_directions()
+        .map(|direction| segment_distance(Vec2::ZERO, direction * radius, offset))
+        .into_iter()
+        .fold(f32::INFINITY, f32::min);
+
+    if range.contains_direction(offset) {
+        circle_distance.max(-edge_distance)
+    } else {
+        edge_distance
+    }
+}
+
+/// Returns the distance from `point` to the arc of the circle around `center` with `radius`
+/// between the angles of `range`.
+pub(crate) fn arc_distance(center: Vec2, radius: f32, range: AngleRange, point: Vec2) -> f32 {
+    let offset = point - center;
+    if range.contains_direction(offset) {
+        return (offset.length() - radius).abs();
+    }
+
+    range
+        .end_directions()
+        .map(|direction| offset.distance(direction * radius))
+        .into_iter()
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// Iterator over the perimeter pixels of a circle between two angles.
+///
+/// The pixels are the ones `CirclePerimeter` yields, walked clockwise from the start angle to the
+/// end angle.
+#[derive(Clone, Debug)]
+pub struct ArcPixels {
+    perimeter: CirclePerimeter,
+    slice: Slice,
+    done: bool,
+}
+
+impl ArcPixels {
+    pub(crate) fn new(circle: &ivec2::Circle, range: AngleRange) -> Self {
+        Self {
+            perimeter: CirclePerimeter::new(circle, range.start, Direction::Clockwise),
+            slice: Slice::new(circle, range),
+            done: false,
+        }
+    }
+}
+
+impl Iterator for ArcPixels {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.perimeter.next() {
+            Some(point) if self.slice.in_range(point) => Some(point),
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+/// Iterator over the pixels of a ring between two circles with the same center, restricted to a
+/// range of angles.
+///
+/// Yields the pixels of `RingPixels` inside the range in scanline order, so arcs with the same
+/// radii and adjacent ranges line up without gaps or overlaps.
+#[derive(Clone, Debug)]
+pub struct ArcRingPixels {
+    ring: RingPixels,
+    slice: Slice,
+}
+
+impl ArcRingPixels {
+    pub(crate) fn new(circle: &ivec2::Circle, inner_radius: i32, range: AngleRange) -> Self {
+        Self {
+            ring: RingPixels::new(circle, inner_radius),
+            slice: Slice::new(circle, range),
+        }
+    }
+}
+
+impl Iterator for ArcRingPixels {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slice = self.slice;
+        self.ring.find(|point| slice.in_range(*point))
+    }
+}
+
+/// Iterator over the pixels of a sector of a circle, a pie slice between two angles.
+///
+/// Pixels are yielded in scanline order. A pixel is filled if it is part of the filled circle
+/// and its center lies inside the range of angles. The outline consists of the filled pixels
+/// next to a pixel that is not filled.
+#[derive(Clone, Debug)]
+pub struct SectorPixels {
+    pixels: CirclePoints,
+    slice: Slice,
+    outline: bool,
+}
+
+impl SectorPixels {
+    pub(crate) fn new(circle: &ivec2::Circle, range: AngleRange, outline: bool) -> Self {
+        Self {
+            pixels: CirclePoints::new(circle, false),
+            slice: Slice::new(circle, range),
+            outline,
+        }
+    }
+}
+
+impl Iterator for SectorPixels {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (slice, outline) = (self.slice, self.outline);
+        self.pixels.find(|point| {
+            slice.in_range(*point) && (!outline || neighbours(*point).iter().any(|n| !slice.in_sector(*n)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    fn circle() -> ivec2::Circle {
+        ivec2::Circle::new(IVec2::new(10, 10), 7)
+    }
+
+    #[test]
+    fn test_full_arc_is_perimeter() {
+        let circle = circle();
+        let range = AngleRange::new(0.0, TAU);
+        let perimeter: Vec<IVec2> = CirclePerimeter::new(&circle, 0.0, Direction::Clockwise).collect();
+
+        assert_eq!(ArcPixels::new(&circle, range).collect::<Vec<_>>(), perimeter);
+        for y in 0..20 {
+            for x in 0..20 {
+                let point = IVec2::new(x, y);
+                assert_eq!(arc_contains(&circle, range, point), perimeter.contains(&point), "{:?}", point);
+            }
+        }
+    }
+
+    #[test]
+    fn test_quarter_arc() {
+        let circle = circle();
+        let range = AngleRange::new(0.0, FRAC_PI_2);
+        let points: Vec<IVec2> = ArcPixels::new(&circle, range).collect();
+
+        // the quarter below and right of the center, walked from the right to the bottom
+        assert_eq!(points.first(), Some(&IVec2::new(16, 10)));
+        assert_eq!(points.last(), Some(&IVec2::new(10, 16)));
+        assert!(points.iter().all(|p| p.x >= 10 && p.y >= 10));
+        assert!(points.iter().all(|p| arc_contains(&circle, range, *p)));
+    }
+
+    #[test]
+    fn test_wrapping_range() {
+        let circle = circle();
+        let wrapped: HashSet<IVec2> = SectorPixels::new(&circle, AngleRange::new(1.5 * PI, 0.5 * PI), false).collect();
+        let right: HashSet<IVec2> = CirclePoints::new(&circle, false).filter(|p| p.x >= 10).collect();
+
+        assert_eq!(wrapped, right);
+    }
+
+    /// Sectors sharing their angles fill the circle without gaps or overlaps.
+    #[test]
+    fn test_sectors_tile_circle() {
+        let circle = circle();
+        let angles = [0.3, 1.7, 2.0, 4.4, 0.3 + TAU];
+        let mut seen = HashSet::new();
+
+        for window in angles.windows(2) {
+            let range = AngleRange::new(window[0], window[1]);
+            for point in SectorPixels::new(&circle, range, false) {
+                assert!(sector_contains(&circle, range, point));
+                assert!(seen.insert(point), "{:?} filled twice", point);
+            }
+        }
+
+        assert_eq!(seen, CirclePoints::new(&circle, false).collect::<HashSet<IVec2>>());
+    }
+
+    #[test]
+    fn test_sector_outline() {
+        let circle = circle();
+        let range = AngleRange::new(0.0, FRAC_PI_2);
+        let filled: HashSet<IVec2> = SectorPixels::new(&circle, range, false).collect();
+        let outline: Vec<IVec2> = SectorPixels::new(&circle, range, true).collect();
+
+        assert!(outline.len() < filled.len());
+        assert!(outline.iter().all(|p| filled.contains(p)));
+        // both straight edges and the curved edge are part of the outline
+        assert!(outline.contains(&IVec2::new(10, 10)));
+        assert!(outline.contains(&IVec2::new(14, 10)));
+        assert!(outline.contains(&IVec2::new(10, 14)));
+        assert!(outline.contains(&IVec2::new(14, 14)));
+        assert!(!outline.contains(&IVec2::new(12, 12)));
+    }
+
+    #[test]
+    fn test_arc_ring() {
+        let circle = circle();
+        let range = AngleRange::new(PI, 1.5 * PI);
+        let points: Vec<IVec2> = ArcRingPixels::new(&circle, 4, range).collect();
+        let ring: Vec<IVec2> = RingPixels::new(&circle, 4).filter(|p| p.x < 10 && p.y < 10).collect();
+
+        assert_eq!(points, ring);
+    }
+
+    #[test]
+    fn test_distances() {
+        let (center, radius) = (Vec2::new(10.0, 10.0), 5.0);
+        let quarter = AngleRange::new(0.0, FRAC_PI_2);
+        let v = Vec2::new;
+
+        // inside, closest to the curved edge and to the straight edges
+        assert_abs_diff_eq!(sector_distance(center, radius, quarter, v(13.0, 10.5)), -0.5, epsilon = 1e-5);
+        assert_abs_diff_eq!(sector_distance(center, radius, quarter, v(12.0, 11.0)), -1.0, epsilon = 1e-5);
+        // outside, beyond the curve and behind the center
+        assert_abs_diff_eq!(sector_distance(center, radius, quarter, v(10.0, 17.0)), 2.0, epsilon = 1e-5);
+        assert_abs_diff_eq!(sector_distance(center, radius, quarter, v(7.0, 6.0)), 5.0, epsilon = 1e-5);
+        assert_abs_diff_eq!(sector_distance(center, radius, quarter, v(12.0, 7.0)), 3.0, epsilon = 1e-5);
+        // a full turn is the circle
+        assert_abs_diff_eq!(sector_distance(center, radius, AngleRange::new(0.0, TAU), v(7.0, 6.0)), 0.0, epsilon = 1e-5);
+
+        assert_abs_diff_eq!(arc_distance(center, radius, quarter, v(13.0, 14.0)), 0.0, epsilon = 1e-5);
+        assert_abs_diff_eq!(arc_distance(center, radius, quarter, v(12.0, 10.0)), 3.0, epsilon = 1e-5);
+        assert_abs_diff_eq!(arc_distance(center, radius, quarter, v(10.0, 17.0)), 2.0, epsilon = 1e-5);
+        // beyond the ends of the arc the closest points are the end points
+        assert_abs_diff_eq!(arc_distance(center, radius, quarter, v(15.0, 7.0)), 3.0, epsilon = 1e-5);
+        assert_abs_diff_eq!(arc_distance(center, radius, quarter, v(6.0, 15.0)), 4.0, epsilon = 1e-5);
+    }
+}
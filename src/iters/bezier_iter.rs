@@ -0,0 +1,286 @@
+use std::collections::HashSet;
+
+use glam::{IVec2, Vec2};
+
+use crate::ivec2::{self, LineDrawAlgo, LineIter};
+
+/// Maximum distance in pixels between a curve and its flattened segments used for drawing.
+pub(crate) const PIXEL_TOLERANCE: f32 = 0.25;
+
+/// Subdivisions deeper than this are flattened regardless of the tolerance.
+const MAX_DEPTH: u32 = 16;
+
+/// Returns the point at `t` of the Bézier curve with the given control points.
+pub(crate) fn point_at(points: &[Vec2], t: f32) -> Vec2 {
+    let mut points = points.to_vec();
+    for len in (1..points.len()).rev() {
+        for i in 0..len {
+            points[i] = points[i].lerp(points[i + 1], t);
+        }
+    }
+    points[0]
+}
+
+/// Splits a Bézier curve at its midpoint using de Casteljau's algorithm.
+fn split(points: &[Vec2]) -> (Vec<Vec2>, Vec<Vec2>) {
+    let mut left = Vec::with_capacity(points.len());
+    let mut right = Vec::with_capacity(points.len());
+    let mut points = points.to_vec();
+
+    for len in (0..points.len()).rev() {
+        left.push(points[0]);
+        right.push(points[len]);
+        for i in 0..len {
+            points[i] = points[i].lerp(points[i + 1], 0.5);
+        }
+    }
+    right.reverse();
+
+    (left, right)
+}
+
+/// Returns `true` if the control points of the curve lie within `tolerance` of its chord, the
+/// curve is then within `tolerance` of the chord as well.
+fn is_flat(points: &[Vec2], tolerance: f32) -> bool {
+    let (start, end) = (points[0], points[points.len() - 1]);
+    points[1..points.len() - 1]
+        .iter()
+        .all(|point| super::sdf_iter::segment_distance(start, end, *point) <= tolerance)
+}
+
+fn flatten_into(points: &[Vec2], tolerance: f32, depth: u32, vertices: &mut Vec<Vec2>) {
+    if depth >= MAX_DEPTH || is_flat(points, tolerance) {
+        vertices.push(points[points.len() - 1]);
+        return;
+    }
+    let (left, right) = split(points);
+    flatten_into(&left, tolerance, depth + 1, vertices);
+    flatten_into(&right, tolerance, depth + 1, vertices);
+}
+
+/// Approximates the Bézier curve with the given control points by a polyline that stays within
+/// `tolerance` of the curve.
+///
+/// The curve is subdivided adaptively, so flat parts use fewer vertices than tight bends. The
+/// first and last vertex are the end points of the curve.
+pub(crate) fn flatten(points: &[Vec2], tolerance: f32) -> Vec<Vec2> {
+    let mut vertices = vec![points[0]];
+    flatten_into(points, tolerance.max(f32::EPSILON), 0, &mut vertices);
+    vertices
+}
+
+/// Returns the distance from `point` to the Bézier curve with the given control points, measured
+/// to the curve flattened to within `PIXEL_TOLERANCE`.
+pub(crate) fn bezier_distance(points: &[Vec2], point: Vec2) -> f32 {
+    flatten(points, PIXEL_TOLERANCE)
+        .windows(2)
+        .map(|pair| super::sdf_iter::segment_distance(pair[0], pair[1], point))
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// Returns the pixels covered by the bounding box of the control points, the curve never leaves
+/// the hull of its control points.
+pub(crate) fn bezier_bounds(points: &[Vec2]) -> ivec2::Rectangle {
+    let min = points.iter().copied().fold(Vec2::INFINITY, Vec2::min);
+    let max = points.iter().copied().fold(Vec2::NEG_INFINITY, Vec2::max);
+    ivec2::Rectangle::new(min.floor().as_ivec2(), max.ceil().as_ivec2())
+}
+
+/// Iterator over the pixels of a Bézier curve as a connected 1px path.
+///
+/// The curve is flattened to within a quarter pixel and the segments are drawn with Bresenham's
+/// algorithm. Consecutive pixels are 8-connected, corners where a diagonal step is enough are cut
+/// and no pixel is yielded twice, even where the curve crosses itself.
+#[derive(Clone, Debug)]
+pub struct BezierPixels {
+    segments: std::vec::IntoIter<ivec2::LineSegment>,
+    current: Option<LineIter>,
+    /// Last yielded pixel.
+    previous: Option<IVec2>,
+    /// Pixel waiting for the next one to decide whether it is a corner that can be cut.
+    pending: Option<IVec2>,
+    visited: HashSet<IVec2>,
+}
+
+impl BezierPixels {
+    pub(crate) fn new(points: &[Vec2]) -> Self {
+        let vertices: Vec<IVec2> = flatten(points, PIXEL_TOLERANCE)
+            .into_iter()
+            .map(|vertex| vertex.round().as_ivec2())
+            .collect();
+
+        let mut segments: Vec<ivec2::LineSegment> = vertices
+            .windows(2)
+            .filter(|pair| pair[0] != pair[1])
+            .map(|pair| ivec2::LineSegment::new(pair[0], pair[1]))
+            .collect();
+        if segments.is_empty() {
+            // a curve inside a single pixel still draws that pixel
+            segments.push(ivec2::LineSegment::new(vertices[0], vertices[0]));
+        }
+
+        Self {
+            segments: segments.into_iter(),
+            current: None,
+            previous: None,
+            pending: None,
+            visited: HashSet::new(),
+        }
+    }
+
+    /// Returns the next pixel of the segments, `None` once all segments are drawn.
+    fn next_point(&mut self) -> Option<IVec2> {
+        loop {
+            if let Some(point) = self.current.as_mut().and_then(|line| line.next()) {
+                return Some(point);
+            }
+            let segment = self.segments.next()?;
+            self.current = Some(LineIter::new_from_segment(segment, LineDrawAlgo::Bresenham));
+        }
+    }
+
+    /// Yields `point` unless it was yielded before.
+    fn emit(&mut self, point: IVec2) -> Option<IVec2> {
+        if !self.visited.insert(point) {
+            return None;
+        }
+        self.previous = Some(point);
+        Some(point)
+    }
+}
+
+impl Iterator for BezierPixels {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(point) = self.next_point() else {
+                let pending = self.pending.take()?;
+                match self.emit(pending) {
+                    Some(point) => return Some(point),
+                    None => continue,
+                }
+            };
+
+            if self.pending == Some(point) || self.previous == Some(point) {
+                continue;
+            }
+            // a corner of three pixels, the middle one can be skipped
+            if self.previous.is_some_and(|previous| (point - previous).abs().max_element() <= 1) {
+                self.pending = Some(point);
+                continue;
+            }
+
+            let Some(pending) = self.pending.replace(point) else {
+                continue;
+            };
+            if let Some(point) = self.emit(pending) {
+                return Some(point);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    fn is_neighbour(a: IVec2, b: IVec2) -> bool {
+        (a - b).abs().max_element() == 1
+    }
+
+    /// Checks that the pixels are unique and each one touches one of the pixels before it.
+    fn assert_connected(points: &[IVec2]) {
+        let unique: HashSet<IVec2> = points.iter().copied().collect();
+        assert_eq!(unique.len(), points.len(), "duplicate pixels");
+
+        for i in 1..points.len() {
+            assert!(points[..i].iter().any(|p| is_neighbour(*p, points[i])), "{:?} is not connected", points[i]);
+        }
+    }
+
+    #[test]
+    fn test_point_at() {
+        let points = [Vec2::new(0.0, 0.0), Vec2::new(10.0, 20.0), Vec2::new(20.0, 0.0)];
+
+        assert_eq!(point_at(&points, 0.0), points[0]);
+        assert_eq!(point_at(&points, 1.0), points[2]);
+        assert_eq!(point_at(&points, 0.5), Vec2::new(10.0, 10.0));
+    }
+
+    #[test]
+    fn test_flatten_tolerance() {
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(5.0, 40.0),
+            Vec2::new(35.0, -20.0),
+            Vec2::new(40.0, 10.0),
+        ];
+
+        for tolerance in [2.0, 0.5, 0.1] {
+            let vertices = flatten(&points, tolerance);
+            assert_eq!(vertices.first(), Some(&points[0]));
+            assert_eq!(vertices.last(), Some(&points[3]));
+
+            for i in 0..=100 {
+                let point = point_at(&points, i as f32 / 100.0);
+                let distance = vertices
+                    .windows(2)
+                    .map(|pair| crate::iters::sdf_iter::segment_distance(pair[0], pair[1], point))
+                    .fold(f32::INFINITY, f32::min);
+                assert!(distance <= tolerance + 1e-4, "{} > {}", distance, tolerance);
+            }
+        }
+
+        let coarse = flatten(&points, 2.0).len();
+        let fine = flatten(&points, 0.1).len();
+        assert!(coarse < fine);
+    }
+
+    #[test]
+    fn test_straight_curve() {
+        let points = [Vec2::new(1.0, 1.0), Vec2::new(5.0, 1.0), Vec2::new(9.0, 1.0)];
+        let vertices = flatten(&points, 0.25);
+        assert_eq!(vertices, vec![points[0], points[2]]);
+
+        let pixels: Vec<IVec2> = BezierPixels::new(&points).collect();
+        assert_eq!(pixels, (1..=9).map(|x| IVec2::new(x, 1)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_connected_path() {
+        let curves: [&[Vec2]; 3] = [
+            &[Vec2::new(0.0, 0.0), Vec2::new(30.0, 0.0), Vec2::new(30.0, 30.0)],
+            &[Vec2::new(2.0, 20.0), Vec2::new(10.0, -10.0), Vec2::new(30.0, 40.0), Vec2::new(40.0, 5.0)],
+            // crosses itself, the pixels at the crossing are only yielded the first time
+            &[Vec2::new(0.0, 10.0), Vec2::new(40.0, 0.0), Vec2::new(0.0, 0.0), Vec2::new(40.0, 10.0)],
+        ];
+
+        for (i, points) in curves.into_iter().enumerate() {
+            let pixels: Vec<IVec2> = BezierPixels::new(points).collect();
+
+            assert_eq!(pixels.first(), Some(&points[0].as_ivec2()));
+            assert_eq!(pixels.last(), Some(&points[points.len() - 1].as_ivec2()));
+            assert_connected(&pixels);
+            if i < 2 {
+                assert!(pixels.windows(2).all(|pair| is_neighbour(pair[0], pair[1])));
+                // no corners where a diagonal step would do
+                assert!(pixels.windows(3).all(|w| !is_neighbour(w[0], w[2])));
+            }
+            for pixel in &pixels {
+                let distance = (0..=200)
+                    .map(|i| point_at(points, i as f32 / 200.0).distance(pixel.as_vec2()))
+                    .fold(f32::INFINITY, f32::min);
+                assert_abs_diff_eq!(distance, 0.0, epsilon = 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_single_pixel() {
+        let points = [Vec2::new(3.1, 3.0), Vec2::new(3.2, 3.3), Vec2::new(2.9, 3.1)];
+        assert_eq!(BezierPixels::new(&points).collect::<Vec<_>>(), vec![IVec2::new(3, 3)]);
+    }
+}
@@ -0,0 +1,212 @@
+use std::ops::Range;
+
+use glam::{IVec2, Vec2};
+
+use super::common::{outline_runs, Scanline};
+use super::sdf_iter::segment_distance;
+use super::thick_line_iter::{closest_on_row, convex_row};
+use crate::ivec2;
+
+/// A line segment swept by a circle, in doubled pixel coordinates.
+///
+/// The end points are positions like the position of a `Circle`, and a pixel is filled if its
+/// center lies inside the radius using the thresholds of `Circle`. A capsule with a zero length
+/// segment therefore has the same pixels as a circle with the same position and radius.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct CapsuleGeometry {
+    start_2x: Vec2,
+    end_2x: Vec2,
+    threshold: f32,
+    bounding_box: ivec2::Rectangle,
+}
+
+impl CapsuleGeometry {
+    /// `threshold` is the squared diameter threshold of the circle swept along the segment.
+    pub(crate) fn new(start: Vec2, end: Vec2, radius: f32, threshold: f32) -> Self {
+        let reach = Vec2::splat(radius.max(0.0));
+
+        Self {
+            start_2x: start * 2.0,
+            end_2x: end * 2.0,
+            threshold,
+            bounding_box: ivec2::Rectangle::new(
+                (start.min(end) - reach).floor().as_ivec2(),
+                (start.max(end) + reach).ceil().as_ivec2(),
+            ),
+        }
+    }
+
+    /// Returns the pixels that can be filled.
+    pub(crate) fn bounding_box(&self) -> ivec2::Rectangle {
+        self.bounding_box
+    }
+
+    pub(crate) fn contains(&self, point: IVec2) -> bool {
+        let center_2x = point.as_vec2() * 2.0 + Vec2::ONE;
+        segment_distance(self.start_2x, self.end_2x, center_2x).powi(2) < self.threshold
+    }
+
+    /// Returns the filled columns of row `y`, the capsule is convex so they are contiguous.
+    fn columns(&self, y: i32) -> Range<i32> {
+        if !self.bounding_box.rows().contains(&y) {
+            return 0..0;
+        }
+        // the filled part of the row contains the pixel center closest to the segment
+        let seed_2x = closest_on_row(self.start_2x, self.end_2x, (y * 2 + 1) as f32);
+        convex_row(y, self.bounding_box.columns(), (seed_2x - 1.0) / 2.0, |point| self.contains(point.as_ivec2()))
+    }
+}
+
+/// Iterator over the pixels of a capsule, a line segment with a radius.
+///
+/// The outline consists of the filled pixels next to a pixel that is not filled, so it has no
+/// gaps.
+#[derive(Clone, Debug)]
+pub struct CapsulePixels {
+    capsule: CapsuleGeometry,
+    outline: bool,
+    rows: Range<i32>,
+    clip: Option<Range<i32>>,
+    current_scanline: Scanline,
+    /// Right side of the outline of the current row.
+    next_scanline: Option<Scanline>,
+}
+
+impl CapsulePixels {
+    pub(crate) fn new(capsule: CapsuleGeometry, outline: bool, clip: Option<ivec2::Rectangle>) -> Self {
+        Self {
+            capsule,
+            outline,
+            rows: capsule.bounding_box.rows_limited(&clip),
+            clip: clip.map(|clip| clip.columns()),
+            current_scanline: Scanline::new_empty(0),
+            next_scanline: None,
+        }
+    }
+
+    fn scanline(&self, y: i32, columns: Range<i32>) -> Scanline {
+        match &self.clip {
+            Some(clip) => Scanline::new_clipped(y, columns, false, clip),
+            None => Scanline::new(y, columns, false),
+        }
+    }
+}
+
+impl Iterator for CapsulePixels {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.current_scanline.next() {
+                return Some(point);
+            }
+            if let Some(scanline) = self.next_scanline.take() {
+                self.current_scanline = scanline;
+                continue;
+            }
+
+            let y = self.rows.next()?;
+            let columns = |y| self.capsule.columns(y);
+            if self.outline {
+                let (left, right) = outline_runs(columns(y), columns(y - 1), columns(y + 1));
+                self.current_scanline = self.scanline(y, left);
+                self.next_scanline = Some(self.scanline(y, right));
+            } else {
+                self.current_scanline = self.scanline(y, columns(y));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn capsule(start: Vec2, end: Vec2, radius: i32) -> CapsuleGeometry {
+        let threshold = ivec2::Circle::new(IVec2::ZERO, radius).threshold();
+        CapsuleGeometry::new(start, end, radius as f32, threshold as f32)
+    }
+
+    #[test]
+    fn test_zero_length_is_circle() {
+        for radius in 1..8 {
+            let pos = IVec2::new(10, 10);
+            let points: Vec<IVec2> = CapsulePixels::new(capsule(pos.as_vec2(), pos.as_vec2(), radius), false, None).collect();
+            let circle: Vec<IVec2> = ivec2::Circle::new(pos, radius).pixel_iter(false).collect();
+
+            assert_eq!(points, circle, "radius {}", radius);
+        }
+    }
+
+    #[test]
+    fn test_horizontal() {
+        let points: HashSet<IVec2> =
+            CapsulePixels::new(capsule(Vec2::new(5.0, 5.0), Vec2::new(15.0, 5.0), 3), false, None).collect();
+
+        // the straight part is as high as the circles at both ends
+        for x in 5..15 {
+            assert!(points.contains(&IVec2::new(x, 2)));
+            assert!(points.contains(&IVec2::new(x, 7)));
+            assert!(!points.contains(&IVec2::new(x, 1)));
+            assert!(!points.contains(&IVec2::new(x, 8)));
+        }
+        assert!(points.contains(&IVec2::new(2, 5)));
+        assert!(points.contains(&IVec2::new(17, 5)));
+        assert!(!points.contains(&IVec2::new(18, 5)));
+    }
+
+    #[test]
+    fn test_outline() {
+        let capsule = capsule(Vec2::new(3.0, 4.0), Vec2::new(17.0, 12.0), 4);
+        let filled: HashSet<IVec2> = CapsulePixels::new(capsule, false, None).collect();
+        let outline: Vec<IVec2> = CapsulePixels::new(capsule, true, None).collect();
+        let expected: HashSet<IVec2> = filled
+            .iter()
+            .copied()
+            .filter(|p| [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y].iter().any(|d| !filled.contains(&(*p + *d))))
+            .collect();
+
+        assert_eq!(outline.len(), expected.len());
+        assert_eq!(outline.into_iter().collect::<HashSet<IVec2>>(), expected);
+        assert!(filled.iter().all(|p| capsule.contains(*p)));
+    }
+
+    #[test]
+    fn test_clipped() {
+        let capsule = capsule(Vec2::new(3.0, 4.0), Vec2::new(17.0, 12.0), 4);
+        let clip = ivec2::Rectangle::new(IVec2::new(5, 0), IVec2::new(12, 9));
+
+        for outline in [false, true] {
+            let expected: Vec<IVec2> = CapsulePixels::new(capsule, outline, None)
+                .filter(|p| clip.columns().contains(&p.x) && clip.rows().contains(&p.y))
+                .collect();
+
+            assert_eq!(CapsulePixels::new(capsule, outline, Some(clip)).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_columns_match_scan() {
+        let ends = [
+            (Vec2::new(3.0, 4.0), Vec2::new(17.0, 12.0)),
+            (Vec2::new(1.0, 1.0), Vec2::new(5.0, 1.0)),
+            (Vec2::new(-4.0, 9.0), Vec2::new(-2.0, -6.0)),
+            (Vec2::new(0.5, 2.5), Vec2::new(30.5, 4.0)),
+            (Vec2::new(6.0, 6.0), Vec2::new(6.0, 6.0)),
+        ];
+
+        for (start, end) in ends {
+            for radius in 1..6 {
+                let capsule = capsule(start, end, radius);
+                let bounding_box = capsule.bounding_box();
+                for y in bounding_box.rows() {
+                    let filled: Vec<i32> = bounding_box.columns().filter(|x| capsule.contains(IVec2::new(*x, y))).collect();
+
+                    assert_eq!(capsule.columns(y).collect::<Vec<i32>>(), filled, "{:?} {:?} {} row {}", start, end, radius, y);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use std::ops::Range;
+
+use glam::{DVec2, IVec2, Vec2};
+
+use crate::ivec2;
+
+/// Iterator over the pixels of a filled circle with a fractional center and radius, yielding
+/// each pixel with the fraction of its area covered by the circle.
+///
+/// Pixels completely inside the circle have a coverage of `1.0`, the coverage of edge pixels is
+/// the exact area of the pixel inside the circle. Pixels without coverage are skipped.
+#[derive(Clone, Debug)]
+pub struct CircleCoverage {
+    center: DVec2,
+    radius: f64,
+    rows: Range<i32>,
+    limits: Option<ivec2::Rectangle>,
+    y: i32,
+    /// Columns of the current row that touch the circle.
+    columns: Range<i32>,
+    /// Columns of the current row that lie completely inside the circle.
+    inside: Range<i32>,
+}
+
+impl CircleCoverage {
+    pub fn new(center: Vec2, radius: f32, limits: Option<ivec2::Rectangle>) -> Self {
+        let radius = radius.max(0.0);
+        let rows = (center.y - radius).floor() as i32..(center.y + radius).ceil() as i32;
+        let rows = match limits {
+            Some(limits) => rows.start.max(limits.tl.y)..rows.end.min(limits.br.y),
+            None => rows,
+        };
+
+        Self {
+            center: center.as_dvec2(),
+            radius: radius as f64,
+            rows,
+            limits,
+            y: 0,
+            columns: 0..0,
+            inside: 0..0,
+        }
+    }
+
+    /// Computes the touched and fully covered columns of row `y`.
+    fn start_row(&mut self, y: i32) {
+        let r = self.radius;
+        let top = y as f64 - self.center.y;
+        let bottom = top + 1.0;
+
+        // the part of the row closest to the center decides which pixels are touched
+        let near = if top <= 0.0 && bottom >= 0.0 { 0.0 } else { top.abs().min(bottom.abs()) };
+        let far = top.abs().max(bottom.abs());
+
+        let reach = (r * r - near * near).max(0.0).sqrt();
+        let mut columns = (self.center.x - reach).floor() as i32..(self.center.x + reach).ceil() as i32;
+
+        let inside = if far < r {
+            let reach = (r * r - far * far).sqrt();
+            (self.center.x - reach).ceil() as i32..(self.center.x + reach).floor() as i32
+        } else {
+            0..0
+        };
+
+        if let Some(limits) = self.limits {
+            columns = columns.start.max(limits.tl.x)..columns.end.min(limits.br.x);
+        }
+
+        self.y = y;
+        self.columns = columns;
+        self.inside = inside;
+    }
+
+    fn coverage(&self, x: i32) -> f32 {
+        if self.inside.contains(&x) {
+            return 1.0;
+        }
+        let tl = DVec2::new(x as f64, self.y as f64) - self.center;
+        let br = tl + 1.0;
+        let r = self.radius;
+
+        let area = quadrant_area(br.x, br.y, r) - quadrant_area(tl.x, br.y, r) - quadrant_area(br.x, tl.y, r)
+            + quadrant_area(tl.x, tl.y, r);
+
+        area.clamp(0.0, 1.0) as f32
+    }
+}
+
+/// Returns the area of the part of a circle around the origin with `x' <= x` and `y' <= y`.
+fn quadrant_area(x: f64, y: f64, r: f64) -> f64 {
+    // integral of the half chord `sqrt(r² - x²)`
+    let half_chord = |x: f64| 0.5 * (x * (r * r - x * x).max(0.0).sqrt() + r * r * (x / r).clamp(-1.0, 1.0).asin());
+    let x = x.clamp(-r, r);
+
+    if r <= 0.0 || y <= -r {
+        return 0.0;
+    }
+    if y >= r {
+        return 2.0 * (half_chord(x) - half_chord(-r));
+    }
+
+    // where the chord is longer than `|y|`, the column is cut off at `y`
+    let c = (r * r - y * y).sqrt();
+    let mut area = 0.0;
+    let end = x.min(c);
+    if end > -c {
+        area += y * (end + c) + half_chord(end) - half_chord(-c);
+    }
+    // above the center, the shorter chords at the sides are covered entirely
+    if y > 0.0 {
+        let end = x.min(-c);
+        if end > -r {
+            area += 2.0 * (half_chord(end) - half_chord(-r));
+        }
+        if x > c {
+            area += 2.0 * (half_chord(x) - half_chord(c));
+        }
+    }
+
+    area
+}
+
+impl Iterator for CircleCoverage {
+    type Item = (IVec2, f32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(x) = self.columns.next() else {
+                let y = self.rows.next()?;
+                self.start_row(y);
+                continue;
+            };
+
+            let coverage = self.coverage(x);
+            if coverage > 0.0 {
+                return Some((IVec2::new(x, self.y), coverage));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_quadrant_area() {
+        let r = 3.0;
+        let full = std::f64::consts::PI * r * r;
+
+        assert_abs_diff_eq!(quadrant_area(5.0, 5.0, r), full, epsilon = 1e-9);
+        assert_abs_diff_eq!(quadrant_area(0.0, 5.0, r), full / 2.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(quadrant_area(0.0, 0.0, r), full / 4.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(quadrant_area(5.0, -1.0, r), full - quadrant_area(5.0, 1.0, r), epsilon = 1e-9);
+        assert_abs_diff_eq!(quadrant_area(-3.0, 2.0, r), 0.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_total_coverage_is_area() {
+        for (center, radius) in [
+            (Vec2::new(10.0, 10.0), 4.0),
+            (Vec2::new(10.3, 9.75), 4.6),
+            (Vec2::new(0.5, 0.5), 0.4),
+        ] {
+            let area: f32 = CircleCoverage::new(center, radius, None).map(|(_, c)| c).sum();
+            assert_abs_diff_eq!(area, std::f32::consts::PI * radius * radius, epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_subpixel_motion() {
+        let coverage = |x: f32| -> f32 {
+            CircleCoverage::new(Vec2::new(x, 5.5), 2.0, None)
+                .filter(|(p, _)| p.x == 7)
+                .map(|(_, c)| c)
+                .sum()
+        };
+
+        // moving the center right gradually covers more of the column right of the circle
+        assert!(coverage(5.0) < coverage(5.25));
+        assert!(coverage(5.25) < coverage(5.5));
+    }
+
+    #[test]
+    fn test_limits() {
+        let limits = ivec2::Rectangle::new(IVec2::new(0, 0), IVec2::new(10, 10));
+        let all: Vec<(IVec2, f32)> = CircleCoverage::new(Vec2::new(10.0, 10.0), 3.5, None).collect();
+        let limited: Vec<(IVec2, f32)> = CircleCoverage::new(Vec2::new(10.0, 10.0), 3.5, Some(limits)).collect();
+
+        let expected: Vec<(IVec2, f32)> = all.into_iter().filter(|(p, _)| p.cmplt(IVec2::splat(10)).all()).collect();
+        assert_eq!(limited, expected);
+        assert_eq!(limited.last().map(|(_, c)| *c), Some(1.0));
+    }
+}
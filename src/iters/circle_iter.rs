@@ -5,17 +5,28 @@ use glam::IVec2;
 
 use super::common::Scanline;
 
+/// Iterator over the pixels of a circle, row by row.
+///
+/// The number of pixels is counted up front, so the iterator reports its exact length and can
+/// also run backwards.
 #[derive(Clone, Debug)]
 pub struct CirclePoints {
     scanlines: Scanlines,
     current_scanline: Scanline,
+    /// Last row, used when iterating backwards.
+    back_scanline: Scanline,
+    /// Number of pixels left to yield.
+    remaining: usize,
 }
 
 impl CirclePoints {
     pub(crate) fn new(circle: &ivec2::Circle, outline: bool) -> Self {
+        let scanlines = Scanlines::new(circle, outline);
         Self {
-            scanlines: Scanlines::new(circle, outline),
+            remaining: scanlines.clone().map(|scanline| scanline.len()).sum(),
+            scanlines,
             current_scanline: Scanline::new_empty(0),
+            back_scanline: Scanline::new_empty(0),
         }
     }
 }
@@ -24,10 +35,75 @@ impl Iterator for CirclePoints {
     type Item = IVec2;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.current_scanline.next().or_else(|| {
-            self.current_scanline = self.scanlines.next()?;
-            self.current_scanline.next()
-        })
+        loop {
+            if let Some(point) = self.current_scanline.next() {
+                self.remaining -= 1;
+                return Some(point);
+            }
+            // limits can leave a row without any pixels, keep going until the rows run out
+            match self.scanlines.next() {
+                Some(scanline) => self.current_scanline = scanline,
+                None => {
+                    let point = self.back_scanline.next()?;
+                    self.remaining -= 1;
+                    return Some(point);
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl DoubleEndedIterator for CirclePoints {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.back_scanline.next_back() {
+                self.remaining -= 1;
+                return Some(point);
+            }
+            match self.scanlines.next_back() {
+                Some(scanline) => self.back_scanline = scanline,
+                None => {
+                    let point = self.current_scanline.next_back()?;
+                    self.remaining -= 1;
+                    return Some(point);
+                }
+            }
+        }
+    }
+}
+
+impl ExactSizeIterator for CirclePoints {}
+
+/// Iterator over the horizontal runs of pixels of a filled circle.
+///
+/// Yields the row and the range of columns of each run, rows without pixels are skipped.
+#[derive(Clone, Debug)]
+pub struct CircleSpans {
+    scanlines: Scanlines,
+}
+
+impl CircleSpans {
+    pub(crate) fn new(circle: &ivec2::Circle) -> Self {
+        Self {
+            scanlines: Scanlines::new(circle, false),
+        }
+    }
+}
+
+impl Iterator for CircleSpans {
+    type Item = (i32, Range<i32>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let scanline = self.scanlines.next()?;
+            if !scanline.is_empty() {
+                return Some((scanline.y, scanline.x));
+            }
+        }
     }
 }
 
@@ -43,7 +119,7 @@ struct Scanlines {
 
 impl Scanlines {
     pub fn new(circle: &ivec2::Circle, outline: bool) -> Self {
-        let bounding_box = circle.bounding_box();
+        let bounding_box = circle.bounds();
         let limits = circle.limits;
         Self {
             outline,
@@ -56,35 +132,59 @@ impl Scanlines {
     }
 }
 
+impl Scanlines {
+    /// Returns the scanline of row `y`.
+    ///
+    /// The first column inside the threshold is estimated from the circle equation and then
+    /// corrected, the row is symmetric so the last column follows from the first.
+    fn scanline(&self, y: i32) -> Scanline {
+        let dy = y * 2 - self.center_2x.y;
+        let inside = |x: i32| {
+            let delta = IVec2::new(x, y) * 2 - self.center_2x;
+            delta.length_squared() < self.threshold
+        };
+        // the doubled offset of a pixel is odd, so the pixels next to the center need more than 1
+        let radius_2x_squared = self.threshold as i64 - (dy as i64).pow(2);
+        if radius_2x_squared <= 1 || self.columns.is_empty() {
+            // rows that miss the circle entirely still have to be skipped
+            return Scanline::new_empty(y);
+        }
+
+        let estimate = ((self.center_2x.x as f64 - (radius_2x_squared as f64).sqrt()) / 2.0).ceil() as i32;
+        let mut x = estimate.clamp(self.columns.start, self.columns.end - 1);
+        while x > self.columns.start && inside(x - 1) {
+            x -= 1;
+        }
+        while x < self.columns.end && !inside(x) {
+            x += 1;
+        }
+        if x == self.columns.end {
+            return Scanline::new_empty(y);
+        }
+
+        // shorten the scanline by right side of the same amount as the left side
+        let last_x = self.columns.end - (x - self.columns.start);
+        if let Some(limits) = self.limits {
+            Scanline::new_clipped(y, x..last_x, self.outline, &limits.columns())
+        } else {
+            Scanline::new(y, x..last_x, self.outline)
+        }
+    }
+}
+
 impl Iterator for Scanlines {
     type Item = Scanline;
 
     fn next(&mut self) -> Option<Self::Item> {
         let y = self.rows.next()?;
+        Some(self.scanline(y))
+    }
+}
 
-
-
-        self.columns
-            .clone()
-            // find first pixel that is inside the threshold
-            .find(|x| {
-                // if *x < self.limits.tl.x || *x >= self.limits.br.x {
-                //     return false;
-                // }
-                let delta = IVec2::new(*x, y) * 2 - self.center_2x;
-                delta.length_squared() < self.threshold
-            })
-            // shorten the scanline by right side of the same amount as the left side
-            .map(|x| { 
-                if let Some(limits) = self.limits {
-                    let start_x = x.max(limits.tl.x);
-                    let last_x = self.columns.end - (x - self.columns.start);
-                    Scanline::new(y, start_x..last_x.min(limits.br.x), self.outline) 
-                } else {
-                    Scanline::new(y, x..self.columns.end - (x - self.columns.start), self.outline) 
-                }
-
-            }) 
+impl DoubleEndedIterator for Scanlines {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let y = self.rows.next_back()?;
+        Some(self.scanline(y))
     }
 }
 
@@ -138,6 +238,104 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_spans() {
+        let circle = ivec2::Circle::new_with_limits(
+            IVec2::new(10, 10),
+            5,
+            ivec2::Rectangle::new(IVec2::new(0, 0), IVec2::new(9, 20)),
+        );
+        let points: Vec<IVec2> = circle
+            .span_iter()
+            .flat_map(|(y, columns)| columns.map(move |x| IVec2::new(x, y)))
+            .collect();
+
+        assert_eq!(points, circle.pixel_iter(false).collect::<Vec<IVec2>>());
+        assert_eq!(circle.span_iter().next(), Some((5, 8..9)));
+    }
+
+    /// The first column of each row is computed directly, it must match a search along the row.
+    #[test]
+    fn test_matches_search() {
+        for radius in 0..40 {
+            let circle = ivec2::Circle::new(IVec2::new(3, -7), radius);
+            let center_2x = circle.center_2x();
+            let bounding_box = circle.bounds();
+            let expected: Vec<IVec2> = bounding_box
+                .rows()
+                .flat_map(|y| bounding_box.columns().map(move |x| IVec2::new(x, y)))
+                .filter(|p| (*p * 2 - center_2x).length_squared() < circle.threshold())
+                .collect();
+
+            assert_eq!(circle.pixel_iter(false).collect::<Vec<_>>(), expected, "radius {}", radius);
+        }
+    }
+
+    #[test]
+    fn test_exact_size_and_reversed() {
+        let limits = ivec2::Rectangle::new(IVec2::new(0, 4), IVec2::new(9, 20));
+        let circles = [
+            ivec2::Circle::new(IVec2::new(10, 10), 5),
+            ivec2::Circle::new_with_limits(IVec2::new(10, 10), 5, limits),
+            ivec2::Circle::new(IVec2::new(10, 10), 0),
+        ];
+
+        for circle in circles {
+            for outline in [false, true] {
+                let points: Vec<IVec2> = circle.pixel_iter(outline).collect();
+                let mut reversed: Vec<IVec2> = circle.pixel_iter(outline).rev().collect();
+                reversed.reverse();
+                assert_eq!(reversed, points);
+
+                let mut iter = circle.pixel_iter(outline);
+                let mut front = Vec::new();
+                let mut back = Vec::new();
+                for len in (0..=points.len()).rev() {
+                    assert_eq!(iter.len(), len);
+                    if len % 3 == 0 {
+                        back.extend(iter.next_back());
+                    } else {
+                        front.extend(iter.next());
+                    }
+                }
+                assert_eq!(iter.next(), None);
+                front.extend(back.into_iter().rev());
+                assert_eq!(front, points);
+            }
+        }
+    }
+
+    /// Rows without pixels inside the clip must not end the iteration early.
+    #[test]
+    fn test_iter_clipped() {
+        use crate::Shape;
+
+        let circle = ivec2::Circle::new(IVec2::new(10, 10), 3);
+        for clip in [
+            ivec2::Rectangle::new(IVec2::new(7, 0), IVec2::new(8, 20)),
+            ivec2::Rectangle::new(IVec2::new(9, 9), IVec2::new(20, 20)),
+            ivec2::Rectangle::new(IVec2::new(0, 0), IVec2::new(5, 5)),
+        ] {
+            for outline in [false, true] {
+                let expected: Vec<IVec2> = circle
+                    .pixel_iter(outline)
+                    .filter(|p| clip.columns().contains(&p.x) && clip.rows().contains(&p.y))
+                    .collect();
+                let points: Vec<IVec2> = if outline {
+                    circle.outline_iter_clipped(clip).collect()
+                } else {
+                    circle.fill_iter_clipped(clip).collect()
+                };
+
+                assert_eq!(points, expected);
+            }
+        }
+        assert_eq!(
+            circle.fill_iter_clipped(ivec2::Rectangle::new(IVec2::new(7, 0), IVec2::new(8, 20))).count(),
+            4
+        );
+    }
+
     // #[test]
     // fn test_iter_with_limits() {
     //     let circle = ivec2::Circle::new_with_limits(
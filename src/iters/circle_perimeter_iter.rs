@@ -0,0 +1,217 @@
+use glam::IVec2;
+
+use crate::ivec2;
+
+/// Direction in which a perimeter is walked, as seen on screen with the y axis pointing down.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Direction {
+    #[default]
+    Clockwise,
+    CounterClockwise,
+}
+
+/// Iterator over the perimeter pixels of a circle in angular order.
+///
+/// The perimeter is found with the midpoint circle algorithm on the doubled coordinates
+/// `CirclePoints` uses, so every pixel lies on the edge of the filled circle. One octant is
+/// computed and mirrored into the others, pixels on the octant boundaries are yielded once.
+///
+/// Angles are in radians, `0.0` points along the positive x axis and angles increase clockwise
+/// on screen. Pixels outside the limits of the circle are skipped.
+#[derive(Clone, Debug)]
+pub struct CirclePerimeter {
+    center_2x: IVec2,
+    /// Offsets of the first quadrant from the doubled center, in clockwise order.
+    quadrant: Vec<IVec2>,
+    direction: Direction,
+    limits: Option<ivec2::Rectangle>,
+    /// Index of the next pixel in clockwise order.
+    index: usize,
+    remaining: usize,
+}
+
+impl CirclePerimeter {
+    pub(crate) fn new(circle: &ivec2::Circle, start_angle: f32, direction: Direction) -> Self {
+        let quadrant = quadrant(circle.threshold());
+        let len = quadrant.len() * 4;
+
+        let mut iter = Self {
+            center_2x: circle.center_2x(),
+            quadrant,
+            direction,
+            limits: circle.limits,
+            index: 0,
+            remaining: len,
+        };
+        if len == 0 {
+            return iter;
+        }
+
+        let start_angle = start_angle.rem_euclid(std::f32::consts::TAU);
+        // first pixel at or after the start angle, walking clockwise
+        let first_after = (0..len).find(|i| iter.angle(*i) >= start_angle).unwrap_or(len);
+        iter.index = match direction {
+            Direction::Clockwise => first_after % len,
+            Direction::CounterClockwise => {
+                let first_past = (first_after..len).find(|i| iter.angle(*i) > start_angle).unwrap_or(len);
+                (first_past + len - 1) % len
+            }
+        };
+
+        iter
+    }
+
+    /// Returns the offset of pixel `index` in clockwise order from the doubled center.
+    fn offset(&self, index: usize) -> IVec2 {
+        let len = self.quadrant.len();
+        let (quadrant, i) = (index / len, index % len);
+
+        match quadrant {
+            0 => self.quadrant[i],
+            1 => self.quadrant[len - 1 - i] * IVec2::new(-1, 1),
+            2 => self.quadrant[i] * -1,
+            _ => self.quadrant[len - 1 - i] * IVec2::new(1, -1),
+        }
+    }
+
+    fn angle(&self, index: usize) -> f32 {
+        let offset = self.offset(index).as_vec2();
+        offset.y.atan2(offset.x).rem_euclid(std::f32::consts::TAU)
+    }
+}
+
+/// Returns the perimeter offsets of the quadrant between the positive x and y axis, ordered by
+/// angle.
+///
+/// Offsets are in doubled coordinates, so both components are odd and no pixel lies on an axis.
+fn quadrant(threshold: i32) -> Vec<IVec2> {
+    let inside = |x: i32, y: i32| x * x + y * y < threshold;
+
+    let mut octant = Vec::new();
+    let mut x = 1;
+    while inside(x + 2, 1) {
+        x += 2;
+    }
+    let mut y = 1;
+    // walk down from the x axis to the diagonal, moving in whenever the pixel leaves the circle
+    while y <= x {
+        while x > 0 && !inside(x, y) {
+            x -= 2;
+        }
+        if x < y {
+            break;
+        }
+        octant.push(IVec2::new(x, y));
+        y += 2;
+    }
+
+    // mirror the octant at the diagonal, without repeating pixels on it
+    let mirrored = octant
+        .iter()
+        .rev()
+        .filter(|offset| offset.x != offset.y)
+        .map(|offset| IVec2::new(offset.y, offset.x));
+    let mut quadrant = octant.clone();
+    quadrant.extend(mirrored);
+
+    quadrant.retain(|offset| inside(offset.x, offset.y));
+    quadrant
+}
+
+impl Iterator for CirclePerimeter {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.quadrant.len() * 4;
+
+        while self.remaining > 0 {
+            self.remaining -= 1;
+
+            let point = (self.center_2x + self.offset(self.index)) / 2;
+            self.index = match self.direction {
+                Direction::Clockwise => (self.index + 1) % len,
+                Direction::CounterClockwise => (self.index + len - 1) % len,
+            };
+
+            match self.limits {
+                Some(limits) if !limits.columns().contains(&point.x) || !limits.rows().contains(&point.y) => {}
+                _ => return Some(point),
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::f32::consts::PI;
+
+    use super::*;
+
+    fn perimeter(radius: i32, start_angle: f32, direction: Direction) -> Vec<IVec2> {
+        let circle = ivec2::Circle::new(IVec2::new(10, 10), radius);
+        CirclePerimeter::new(&circle, start_angle, direction).collect()
+    }
+
+    #[test]
+    fn test_small() {
+        assert_eq!(
+            perimeter(1, 0.0, Direction::Clockwise),
+            vec![IVec2::new(10, 10), IVec2::new(9, 10), IVec2::new(9, 9), IVec2::new(10, 9)]
+        );
+        assert!(perimeter(0, 0.0, Direction::Clockwise).is_empty());
+    }
+
+    #[test]
+    fn test_edge_of_filled_circle() {
+        for radius in 1..20 {
+            let circle = ivec2::Circle::new(IVec2::new(10, 10), radius);
+            let filled: HashSet<IVec2> = circle.pixel_iter(false).collect();
+            let points = perimeter(radius, 0.0, Direction::Clockwise);
+            let unique: HashSet<IVec2> = points.iter().copied().collect();
+
+            assert_eq!(unique.len(), points.len(), "duplicates for radius {}", radius);
+            for point in &points {
+                assert!(filled.contains(point));
+                let neighbours = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y].map(|d| *point + d);
+                assert!(neighbours.iter().any(|n| !filled.contains(n)), "{:?} is not on the edge", point);
+            }
+
+            // consecutive pixels are 8-connected, including the wrap around
+            for i in 0..points.len() {
+                let step = (points[(i + 1) % points.len()] - points[i]).abs();
+                assert!(step.max_element() == 1, "radius {} step {:?}", radius, step);
+            }
+        }
+    }
+
+    #[test]
+    fn test_start_angle_and_direction() {
+        let clockwise = perimeter(6, 0.0, Direction::Clockwise);
+        // straight down on screen is a quarter turn clockwise, the first pixel is just past it
+        let down = perimeter(6, PI / 2.0, Direction::Clockwise);
+        assert_eq!(down[0], IVec2::new(9, 15));
+        assert_eq!(down.len(), clockwise.len());
+
+        let counter_clockwise = perimeter(6, 0.0, Direction::CounterClockwise);
+        assert_eq!(counter_clockwise[0], IVec2::new(15, 9));
+        assert_eq!(
+            counter_clockwise[1..].iter().rev().collect::<Vec<_>>(),
+            clockwise[..clockwise.len() - 1].iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let limits = ivec2::Rectangle::new(IVec2::new(0, 0), IVec2::new(12, 8));
+        let circle = ivec2::Circle::new_with_limits(IVec2::new(10, 10), 5, limits);
+        let expected: Vec<IVec2> = perimeter(5, 1.0, Direction::Clockwise)
+            .into_iter()
+            .filter(|p| limits.columns().contains(&p.x) && limits.rows().contains(&p.y))
+            .collect();
+
+        assert_eq!(CirclePerimeter::new(&circle, 1.0, Direction::Clockwise).collect::<Vec<_>>(), expected);
+    }
+}
@@ -2,6 +2,8 @@ use std::ops::Range;
 
 use glam::IVec2;
 
+use crate::ivec2;
+
 /// Scanline.
 #[derive(Debug, Clone)]
 pub struct Scanline {
@@ -19,6 +21,21 @@ impl Scanline {
         Self { y, x: x.into_iter(), outline, outline_started: false, outline_finished: false }
     }
 
+    /// Creates a new scanline that only yields the pixels inside the `clip` columns.
+    ///
+    /// Outline scanlines keep the end points of the unclipped span, so clipping never adds
+    /// outline pixels along the edges of the clip rectangle.
+    pub fn new_clipped(y: i32, x: Range<i32>, outline: bool, clip: &Range<i32>) -> Self {
+        if outline {
+            let mut scanline = Self::new(y, x, true);
+            scanline.outline_started = !clip.contains(&scanline.x.start);
+            scanline.outline_finished = !clip.contains(&(scanline.x.end - 1));
+            scanline
+        } else {
+            Self::new(y, x.start.max(clip.start)..x.end.min(clip.end), false)
+        }
+    }
+
     /// Creates a new empty scanline.
     pub fn new_empty(y: i32) -> Self {
         Self::new(y, 0..0, false)
@@ -28,6 +45,17 @@ impl Scanline {
     pub fn is_empty(&self) -> bool {
         self.x.is_empty()
     }
+
+    /// Returns the number of pixels the scanline has left to yield.
+    pub fn len(&self) -> usize {
+        if !self.outline {
+            self.x.len()
+        } else if self.is_empty() {
+            0
+        } else {
+            !self.outline_started as usize + (!self.outline_finished && self.x.len() > 1) as usize
+        }
+    }
 }
 
 impl Iterator for Scanline {
@@ -35,10 +63,12 @@ impl Iterator for Scanline {
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.outline {
-            if !self.outline_started {
+            if self.is_empty() {
+                None
+            } else if !self.outline_started {
                 self.outline_started = true;
                 Some(IVec2::new(self.x.start, self.y))
-            } else if !self.outline_finished {
+            } else if !self.outline_finished && self.x.len() > 1 {
                 self.outline_finished = true;
                 Some(IVec2::new(self.x.end - 1, self.y))
             } else {
@@ -48,8 +78,58 @@ impl Iterator for Scanline {
             self.x.next().map(|x| IVec2::new(x, self.y))
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
 }
 
+impl DoubleEndedIterator for Scanline {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.outline {
+            if self.is_empty() {
+                None
+            } else if !self.outline_finished && self.x.len() > 1 {
+                self.outline_finished = true;
+                Some(IVec2::new(self.x.end - 1, self.y))
+            } else if !self.outline_started {
+                self.outline_started = true;
+                Some(IVec2::new(self.x.start, self.y))
+            } else {
+                None
+            }
+        } else {
+            self.x.next_back().map(|x| IVec2::new(x, self.y))
+        }
+    }
+}
+
+/// Returns the outline pixels of a row of a convex shape, the pixels of `columns` that are not
+/// covered by both the row `above` and the row `below`.
+///
+/// The outline of a row is split into a left and a right run, the right run is empty if the row
+/// has only one.
+pub(crate) fn outline_runs(columns: Range<i32>, above: Range<i32>, below: Range<i32>) -> (Range<i32>, Range<i32>) {
+    let inner = above.start.max(below.start)..above.end.min(below.end);
+    if columns.is_empty() || inner.is_empty() {
+        return (columns, 0..0);
+    }
+
+    let left = columns.start..inner.start.clamp(columns.start + 1, columns.end);
+    let right = inner.end.min(columns.end - 1).max(left.end)..columns.end;
+
+    (left, right)
+}
+
+/// Combines the optional limits of a shape with a clip rectangle.
+///
+/// Returns an empty rectangle if the two don't overlap.
+pub(crate) fn clip_limits(limits: Option<ivec2::Rectangle>, clip: ivec2::Rectangle) -> ivec2::Rectangle {
+    match limits {
+        Some(limits) => limits.intersection(&clip).unwrap_or_default(),
+        None => clip,
+    }
+}
 
 // /// Scanline.
 // #[derive(Debug, Clone)]
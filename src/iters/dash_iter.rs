@@ -0,0 +1,171 @@
+use glam::IVec2;
+
+/// A repeating pattern of dashes and gaps along a line, measured in pixels.
+///
+/// `runs` alternates between the length of a dash and the length of the gap after it, starting
+/// with a dash. A pattern with an odd number of runs is repeated once to make it even, so `[2]`
+/// is the same as `[2, 2]`. An empty pattern, or one without any length, draws a solid line.
+///
+/// The phase shifts the pattern along the line: with a phase of `n` the line starts `n` pixels
+/// into the pattern. Changing the phase by one pixel per frame animates "marching ants".
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct DashPattern {
+    runs: Vec<u32>,
+    phase: i32,
+}
+
+impl DashPattern {
+    pub fn new(runs: &[u32]) -> Self {
+        let mut runs = runs.to_vec();
+        if runs.len() % 2 == 1 {
+            runs.extend_from_within(..);
+        }
+
+        Self { runs, phase: 0 }
+    }
+
+    /// Returns the pattern starting `phase` pixels into it.
+    pub fn with_phase(mut self, phase: i32) -> Self {
+        self.phase = phase;
+        self
+    }
+
+    pub fn set_phase(&mut self, phase: i32) {
+        self.phase = phase;
+    }
+
+    pub fn phase(&self) -> i32 {
+        self.phase
+    }
+
+    /// Returns the length of one repetition of the pattern.
+    pub fn period(&self) -> u32 {
+        self.runs.iter().sum()
+    }
+
+    /// Returns `true` if the pixel at `position` along the line is part of a dash.
+    pub fn is_on(&self, position: u32) -> bool {
+        let period = self.period() as i64;
+        if period == 0 {
+            return true;
+        }
+
+        let mut offset = (position as i64 + self.phase as i64).rem_euclid(period);
+        for (i, run) in self.runs.iter().enumerate() {
+            if offset < *run as i64 {
+                return i % 2 == 0;
+            }
+            offset -= *run as i64;
+        }
+        unreachable!("the offset is less than the period")
+    }
+}
+
+/// Iterator that applies a `DashPattern` to the pixels of a path.
+///
+/// The pattern advances by one for every pixel of the wrapped iterator, so it continues across
+/// the segments and corners of a polyline or the outline of a rectangle. Pixels in the gaps of
+/// the pattern are skipped.
+#[derive(Clone, Debug)]
+pub struct Dashed<I> {
+    pixels: I,
+    pattern: DashPattern,
+    position: u32,
+}
+
+impl<I: Iterator<Item = IVec2>> Dashed<I> {
+    pub fn new(pixels: I, pattern: DashPattern) -> Self {
+        Self {
+            pixels,
+            pattern,
+            position: 0,
+        }
+    }
+}
+
+impl<I: Iterator<Item = IVec2>> Iterator for Dashed<I> {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let point = self.pixels.next()?;
+            let on = self.pattern.is_on(self.position);
+            self.position = self.position.wrapping_add(1);
+            if on {
+                return Some(point);
+            }
+        }
+    }
+}
+
+/// Adds `dashed` to iterators over the pixels of a path, such as `LineIter`.
+pub trait DashExt: Iterator<Item = IVec2> + Sized {
+    /// Returns an iterator over the pixels that fall on the dashes of `pattern`.
+    fn dashed(self, pattern: DashPattern) -> Dashed<Self> {
+        Dashed::new(self, pattern)
+    }
+}
+
+impl<I: Iterator<Item = IVec2>> DashExt for I {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::iters::polyline_iter::PolylinePixels;
+    use crate::ivec2::{LineDrawAlgo, LineIter, LineSegment};
+
+    fn line(length: i32) -> LineIter {
+        LineIter::new_from_segment(LineSegment::new(IVec2::ZERO, IVec2::new(length - 1, 0)), LineDrawAlgo::Bresenham)
+    }
+
+    fn columns(pixels: impl Iterator<Item = IVec2>) -> Vec<i32> {
+        pixels.map(|p| p.x).collect()
+    }
+
+    #[test]
+    fn test_pattern() {
+        let pattern = DashPattern::new(&[3, 2]);
+        assert_eq!(columns(line(12).dashed(pattern)), vec![0, 1, 2, 5, 6, 7, 10, 11]);
+
+        // odd patterns repeat, `[1]` is a dotted line
+        assert_eq!(columns(line(7).dashed(DashPattern::new(&[1]))), vec![0, 2, 4, 6]);
+        assert_eq!(DashPattern::new(&[1, 2, 3]).period(), 12);
+
+        assert_eq!(line(5).dashed(DashPattern::new(&[])).count(), 5);
+        assert_eq!(line(5).dashed(DashPattern::new(&[0, 0])).count(), 5);
+    }
+
+    #[test]
+    fn test_phase() {
+        let pattern = DashPattern::new(&[3, 2]).with_phase(1);
+        assert_eq!(columns(line(10).dashed(pattern)), vec![0, 1, 4, 5, 6, 9]);
+
+        // a phase of one period is the same as no phase, negative phases move the other way
+        let pattern = DashPattern::new(&[3, 2]);
+        assert_eq!(
+            columns(line(20).dashed(pattern.clone().with_phase(5))),
+            columns(line(20).dashed(pattern.clone()))
+        );
+        assert_eq!(
+            columns(line(20).dashed(pattern.clone().with_phase(-1))),
+            columns(line(20).dashed(pattern.with_phase(4)))
+        );
+    }
+
+    /// The pattern carries on around corners instead of restarting with each segment.
+    #[test]
+    fn test_across_corners() {
+        let vertices = [IVec2::new(0, 0), IVec2::new(4, 0), IVec2::new(4, 4), IVec2::new(0, 4)];
+        let pattern = DashPattern::new(&[2, 1]);
+        let path: Vec<IVec2> = PolylinePixels::new(&vertices, false, LineDrawAlgo::Bresenham, None).collect();
+        let dashed: Vec<IVec2> = PolylinePixels::new(&vertices, false, LineDrawAlgo::Bresenham, None)
+            .dashed(pattern)
+            .collect();
+
+        let expected: Vec<IVec2> = path.iter().enumerate().filter(|(i, _)| i % 3 != 2).map(|(_, p)| *p).collect();
+        assert_eq!(dashed, expected);
+        // restarting the pattern at the corner would draw (4, 1)
+        assert!(dashed.contains(&IVec2::new(4, 0)));
+        assert!(!dashed.contains(&IVec2::new(4, 1)));
+    }
+}
@@ -0,0 +1,213 @@
+use std::ops::Range;
+
+use crate::ivec2;
+use glam::{I64Vec2, IVec2};
+
+use super::common::Scanline;
+
+/// Iterator over the pixels of an ellipse, one scanline at a time.
+#[derive(Clone, Debug)]
+pub struct EllipsePoints {
+    scanlines: Scanlines,
+    current_scanline: Scanline,
+}
+
+impl EllipsePoints {
+    pub(crate) fn new(ellipse: &ivec2::Ellipse, outline: bool) -> Self {
+        Self {
+            scanlines: Scanlines::new(ellipse, outline),
+            current_scanline: Scanline::new_empty(0),
+        }
+    }
+}
+
+impl Iterator for EllipsePoints {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.current_scanline.next() {
+                return Some(point);
+            }
+            // limits can leave a row without any pixels, keep going until the rows run out
+            self.current_scanline = self.scanlines.next()?;
+        }
+    }
+}
+
+/// Iterator over the horizontal runs of pixels of a filled ellipse.
+///
+/// Yields the row and the range of columns of each run, rows without pixels are skipped.
+#[derive(Clone, Debug)]
+pub struct EllipseSpans {
+    scanlines: Scanlines,
+}
+
+impl EllipseSpans {
+    pub(crate) fn new(ellipse: &ivec2::Ellipse) -> Self {
+        Self {
+            scanlines: Scanlines::new(ellipse, false),
+        }
+    }
+}
+
+impl Iterator for EllipseSpans {
+    type Item = (i32, Range<i32>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let scanline = self.scanlines.next()?;
+            if !scanline.is_empty() {
+                return Some((scanline.y, scanline.x));
+            }
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct Scanlines {
+    outline: bool,
+    rows: Range<i32>,
+    columns: Range<i32>,
+    center_2x: IVec2,
+    radius: I64Vec2,
+    threshold: i64,
+    limits: Option<ivec2::Rectangle>,
+}
+
+impl Scanlines {
+    pub fn new(ellipse: &ivec2::Ellipse, outline: bool) -> Self {
+        let bounding_box = ellipse.bounds();
+        let limits = ellipse.limits;
+        Self {
+            outline,
+            rows: bounding_box.rows_limited(&limits),
+            columns: bounding_box.columns(),
+            center_2x: ellipse.center_2x(),
+            radius: ellipse.radius.as_i64vec2(),
+            threshold: ellipse.threshold(),
+            limits,
+        }
+    }
+}
+
+impl Iterator for Scanlines {
+    type Item = Scanline;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let y = self.rows.next()?;
+        let delta_y = (y * 2 - self.center_2x.y) as i64;
+        let distance_y = delta_y.pow(2) * self.radius.x.pow(2);
+
+        self.columns
+            .clone()
+            // find first pixel that is inside the threshold
+            .find(|x| {
+                let delta_x = (x * 2 - self.center_2x.x) as i64;
+                delta_x.pow(2) * self.radius.y.pow(2) + distance_y < self.threshold
+            })
+            // shorten the scanline by right side of the same amount as the left side
+            .map(|x| {
+                let last_x = self.columns.end - (x - self.columns.start);
+                if let Some(limits) = self.limits {
+                    Scanline::new_clipped(y, x..last_x, self.outline, &limits.columns())
+                } else {
+                    Scanline::new(y, x..last_x, self.outline)
+                }
+            })
+            // rows that miss the ellipse entirely still have to be skipped
+            .or_else(|| Some(Scanline::new_empty(y)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_iter() {
+        let ellipse = ivec2::Ellipse::new(IVec2::new(10, 10), IVec2::new(3, 2));
+        let points: Vec<IVec2> = ellipse.pixel_iter(false).collect();
+
+        assert_eq!(
+            points,
+            vec![
+                IVec2::new(8, 8),
+                IVec2::new(9, 8),
+                IVec2::new(10, 8),
+                IVec2::new(11, 8),
+                IVec2::new(7, 9),
+                IVec2::new(8, 9),
+                IVec2::new(9, 9),
+                IVec2::new(10, 9),
+                IVec2::new(11, 9),
+                IVec2::new(12, 9),
+                IVec2::new(7, 10),
+                IVec2::new(8, 10),
+                IVec2::new(9, 10),
+                IVec2::new(10, 10),
+                IVec2::new(11, 10),
+                IVec2::new(12, 10),
+                IVec2::new(8, 11),
+                IVec2::new(9, 11),
+                IVec2::new(10, 11),
+                IVec2::new(11, 11),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_equal_radii_match_circle() {
+        for radius in 1..12 {
+            let pos = IVec2::new(20, 15);
+            let ellipse = ivec2::Ellipse::new(pos, IVec2::splat(radius));
+            let circle = ivec2::Circle::new(pos, radius);
+
+            assert_eq!(
+                ellipse.pixel_iter(false).collect::<Vec<IVec2>>(),
+                circle.pixel_iter(false).collect::<Vec<IVec2>>(),
+            );
+        }
+    }
+
+    #[test]
+    fn test_iter_with_limits() {
+        let ellipse = ivec2::Ellipse::new_with_limits(
+            IVec2::new(10, 10),
+            IVec2::new(3, 2),
+            ivec2::Rectangle::new(IVec2::new(10, 10), IVec2::new(20, 20)),
+        );
+        let points: Vec<IVec2> = ellipse.pixel_iter(false).collect();
+
+        assert_eq!(
+            points,
+            vec![
+                IVec2::new(10, 10),
+                IVec2::new(11, 10),
+                IVec2::new(12, 10),
+                IVec2::new(10, 11),
+                IVec2::new(11, 11),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_outline() {
+        let ellipse = ivec2::Ellipse::new(IVec2::new(10, 10), IVec2::new(3, 2));
+        let points: Vec<IVec2> = ellipse.pixel_iter(true).collect();
+
+        assert_eq!(
+            points,
+            vec![
+                IVec2::new(8, 8),
+                IVec2::new(11, 8),
+                IVec2::new(7, 9),
+                IVec2::new(12, 9),
+                IVec2::new(7, 10),
+                IVec2::new(12, 10),
+                IVec2::new(8, 11),
+                IVec2::new(11, 11),
+            ]
+        );
+    }
+}
@@ -1,5 +1,20 @@
 pub mod common;
+pub mod arc_iter;
+pub mod bezier_iter;
+pub mod capsule_iter;
 pub mod rect_iter;
 pub mod circle_iter;
+pub mod circle_coverage_iter;
+pub mod circle_perimeter_iter;
+pub mod dash_iter;
+pub mod ellipse_iter;
+pub mod polygon_iter;
+pub mod polyline_iter;
+pub mod ring_iter;
+pub mod rounded_rect_iter;
+pub mod sdf_iter;
+pub mod thick_line_iter;
+pub mod triangle_iter;
+pub mod wu_line_iter;
 // pub mod line_iter;
 // pub mod line_iter_vec2;
\ No newline at end of file
@@ -0,0 +1,367 @@
+use std::ops::Range;
+
+use glam::{IVec2, Vec2};
+
+use crate::ivec2;
+
+use super::common::Scanline;
+
+/// Rule used to decide which parts of a self intersecting polygon are inside.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum FillRule {
+    /// A point is inside if a ray from it crosses the outline an odd number of times.
+    EvenOdd,
+    /// A point is inside if the outline winds around it at least once.
+    #[default]
+    NonZero,
+}
+
+impl FillRule {
+    /// Returns `true` if a point with the given winding number and crossing count is inside.
+    fn is_inside(&self, winding: i32, crossings: usize) -> bool {
+        match self {
+            FillRule::EvenOdd => crossings % 2 == 1,
+            FillRule::NonZero => winding != 0,
+        }
+    }
+}
+
+/// Returns `true` if the pixel at `coord` is filled by the polygon.
+///
+/// The pixel center is tested with the same edge rules `PolygonPixels` uses, so hit testing
+/// always matches what gets drawn.
+pub(crate) fn contains(vertices: &[Vec2], fill_rule: FillRule, coord: Vec2) -> bool {
+    let center = coord + Vec2::splat(0.5);
+    let mut winding = 0;
+    let mut crossings = 0;
+
+    for (x, direction) in edges(vertices).filter_map(|edge| edge.crossing(center.y)) {
+        if x <= center.x {
+            winding += direction;
+            crossings += 1;
+        }
+    }
+
+    fill_rule.is_inside(winding, crossings)
+}
+
+/// Iterator over the pixels inside a polygon.
+#[derive(Clone, Debug)]
+pub struct PolygonPixels {
+    scanlines: Scanlines,
+    current_scanline: Scanline,
+}
+
+impl PolygonPixels {
+    pub(crate) fn new(
+        vertices: Vec<Vec2>,
+        fill_rule: FillRule,
+        limits: Option<ivec2::Rectangle>,
+        outline: bool,
+    ) -> Self {
+        Self {
+            scanlines: Scanlines::new(vertices, fill_rule, limits, outline),
+            current_scanline: Scanline::new_empty(0),
+        }
+    }
+}
+
+impl Iterator for PolygonPixels {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.current_scanline.next() {
+                return Some(point);
+            }
+            self.current_scanline = self.scanlines.next()?;
+        }
+    }
+}
+
+/// Iterator over the horizontal runs of pixels inside a polygon.
+///
+/// Yields the row and the range of columns of each run, from left to right. Rows crossing a hole
+/// or a concave part of the polygon yield several runs.
+#[derive(Clone, Debug)]
+pub struct PolygonSpans {
+    scanlines: Scanlines,
+}
+
+impl PolygonSpans {
+    pub(crate) fn new(vertices: Vec<Vec2>, fill_rule: FillRule, limits: Option<ivec2::Rectangle>) -> Self {
+        Self {
+            scanlines: Scanlines::new(vertices, fill_rule, limits, false),
+        }
+    }
+}
+
+impl Iterator for PolygonSpans {
+    type Item = (i32, Range<i32>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let scanline = self.scanlines.next()?;
+            if !scanline.is_empty() {
+                return Some((scanline.y, scanline.x));
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Edge {
+    start: Vec2,
+    end: Vec2,
+}
+
+impl Edge {
+    /// Returns the x coordinate and direction where the edge crosses the horizontal line at `y`.
+    ///
+    /// Edges are treated as half open in y, so a vertex shared by two edges is only counted once.
+    fn crossing(&self, y: f32) -> Option<(f32, i32)> {
+        let (top, bottom, direction) = if self.start.y < self.end.y {
+            (self.start, self.end, 1)
+        } else {
+            (self.end, self.start, -1)
+        };
+
+        if y < top.y || y >= bottom.y {
+            return None;
+        }
+
+        let x = top.x + (y - top.y) * (bottom.x - top.x) / (bottom.y - top.y);
+        Some((x, direction))
+    }
+}
+
+fn edges(vertices: &[Vec2]) -> impl Iterator<Item = Edge> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(start, end)| Edge { start: *start, end: *end })
+}
+
+#[derive(Clone, Debug)]
+struct Scanlines {
+    outline: bool,
+    fill_rule: FillRule,
+    edges: Vec<Edge>,
+    rows: Range<i32>,
+    limits: Option<ivec2::Rectangle>,
+    crossings: Vec<(f32, i32)>,
+    pending: Vec<Scanline>,
+}
+
+impl Scanlines {
+    fn new(
+        vertices: Vec<Vec2>,
+        fill_rule: FillRule,
+        limits: Option<ivec2::Rectangle>,
+        outline: bool,
+    ) -> Self {
+        let (min_y, max_y) = vertices
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(min, max), v| (min.min(v.y), max.max(v.y)));
+
+        // rows whose pixel centers lie between the top and bottom vertex
+        let rows = if vertices.len() < 3 {
+            0..0
+        } else {
+            (min_y - 0.5).ceil() as i32..(max_y - 0.5).ceil() as i32
+        };
+        let rows = match limits {
+            Some(limits) => rows.start.max(limits.tl.y)..rows.end.min(limits.br.y),
+            None => rows,
+        };
+
+        Self {
+            outline,
+            fill_rule,
+            edges: edges(&vertices).collect(),
+            rows,
+            limits,
+            crossings: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Fills `pending` with the spans of row `y`, in reverse order so they can be popped.
+    fn fill_row(&mut self, y: i32) {
+        let center_y = y as f32 + 0.5;
+
+        self.crossings.clear();
+        self.crossings
+            .extend(self.edges.iter().filter_map(|edge| edge.crossing(center_y)));
+        self.crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut winding = 0;
+        let mut span_start = None;
+
+        for (count, (x, direction)) in self.crossings.iter().enumerate() {
+            winding += direction;
+            let inside = self.fill_rule.is_inside(winding, count + 1);
+
+            match (span_start, inside) {
+                (None, true) => span_start = Some(*x),
+                (Some(start), false) => {
+                    span_start = None;
+
+                    // pixels whose centers lie in `start..x`
+                    let columns = (start - 0.5).ceil() as i32..(x - 0.5).ceil() as i32;
+                    if !columns.is_empty() {
+                        self.pending.push(match self.limits {
+                            Some(limits) => Scanline::new_clipped(y, columns, self.outline, &limits.columns()),
+                            None => Scanline::new(y, columns, self.outline),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self.pending.reverse();
+    }
+}
+
+impl Iterator for Scanlines {
+    type Item = Scanline;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            let y = self.rows.next()?;
+            self.fill_row(y);
+        }
+
+        self.pending.pop()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn square() -> Vec<Vec2> {
+        vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(4.0, 0.0),
+            Vec2::new(4.0, 4.0),
+            Vec2::new(0.0, 4.0),
+        ]
+    }
+
+    #[test]
+    fn test_square_matches_rectangle() {
+        let rect = ivec2::Rectangle::new(IVec2::new(0, 0), IVec2::new(4, 4));
+        let pixels: Vec<IVec2> = PolygonPixels::new(square(), FillRule::NonZero, None, false).collect();
+
+        assert_eq!(pixels, rect.pixel_iter(false).collect::<Vec<IVec2>>());
+    }
+
+    #[test]
+    fn test_triangle() {
+        let vertices = vec![Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(0.0, 4.0)];
+        let pixels: Vec<IVec2> = PolygonPixels::new(vertices, FillRule::EvenOdd, None, false).collect();
+
+        assert_eq!(
+            pixels,
+            vec![
+                IVec2::new(0, 0),
+                IVec2::new(1, 0),
+                IVec2::new(2, 0),
+                IVec2::new(0, 1),
+                IVec2::new(1, 1),
+                IVec2::new(0, 2),
+            ]
+        );
+    }
+
+    /// Two overlapping squares traced in the same direction, the overlap is a hole with
+    /// `EvenOdd` and filled with `NonZero`.
+    fn overlapping_squares() -> Vec<Vec2> {
+        vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(4.0, 0.0),
+            Vec2::new(4.0, 4.0),
+            Vec2::new(0.0, 4.0),
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(6.0, 2.0),
+            Vec2::new(6.0, 6.0),
+            Vec2::new(2.0, 6.0),
+            Vec2::new(2.0, 2.0),
+        ]
+    }
+
+    #[test]
+    fn test_fill_rules() {
+        let even_odd: Vec<IVec2> =
+            PolygonPixels::new(overlapping_squares(), FillRule::EvenOdd, None, false).collect();
+        let non_zero: Vec<IVec2> =
+            PolygonPixels::new(overlapping_squares(), FillRule::NonZero, None, false).collect();
+
+        assert!(!even_odd.contains(&IVec2::new(3, 3)));
+        assert!(non_zero.contains(&IVec2::new(3, 3)));
+        assert_eq!(even_odd.len(), 16 + 16 - 8);
+        assert_eq!(non_zero.len(), 16 + 16 - 4);
+    }
+
+    #[test]
+    fn test_contains_matches_pixels() {
+        for fill_rule in [FillRule::EvenOdd, FillRule::NonZero] {
+            let vertices = overlapping_squares();
+            let pixels: Vec<IVec2> =
+                PolygonPixels::new(vertices.clone(), fill_rule, None, false).collect();
+
+            for y in -1..8 {
+                for x in -1..8 {
+                    let coord = IVec2::new(x, y);
+                    assert_eq!(
+                        contains(&vertices, fill_rule, coord.as_vec2()),
+                        pixels.contains(&coord),
+                        "{:?} {:?}",
+                        fill_rule,
+                        coord
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_spans() {
+        // a U shape, the middle rows have two runs
+        let vertices = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(4.0, 2.0),
+            Vec2::new(4.0, 0.0),
+            Vec2::new(6.0, 0.0),
+            Vec2::new(6.0, 3.0),
+            Vec2::new(0.0, 3.0),
+        ];
+        let spans: Vec<(i32, Range<i32>)> = PolygonSpans::new(vertices, FillRule::NonZero, None).collect();
+
+        assert_eq!(spans, vec![(0, 0..2), (0, 4..6), (1, 0..2), (1, 4..6), (2, 0..6)]);
+    }
+
+    #[test]
+    fn test_limits() {
+        let limits = ivec2::Rectangle::new(IVec2::new(1, 1), IVec2::new(3, 10));
+        let pixels: Vec<IVec2> =
+            PolygonPixels::new(square(), FillRule::NonZero, Some(limits), false).collect();
+
+        assert_eq!(
+            pixels,
+            vec![
+                IVec2::new(1, 1),
+                IVec2::new(2, 1),
+                IVec2::new(1, 2),
+                IVec2::new(2, 2),
+                IVec2::new(1, 3),
+                IVec2::new(2, 3),
+            ]
+        );
+    }
+}
@@ -0,0 +1,468 @@
+use std::collections::HashSet;
+use std::ops::Range;
+
+use glam::{IVec2, Vec2};
+
+use crate::ivec2::{self, LineDrawAlgo, LineIter};
+
+use super::common::Scanline;
+use super::thick_line_iter::{convex_row, LineCap, ThickSegment};
+
+/// Shape used where two segments of a thick polyline meet.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum LineJoin {
+    /// The outer edges are extended until they meet, falling back to `Bevel` for very sharp
+    /// corners.
+    #[default]
+    Miter,
+    /// The outer corners are connected by a straight edge.
+    Bevel,
+    /// A circle with the line width as diameter is added at the joint.
+    Round,
+}
+
+/// Corners sharper than this ratio of miter length to half the line width are beveled.
+const MITER_LIMIT: f32 = 4.0;
+
+/// Returns the segments between consecutive vertices, skipping zero length ones.
+fn segments<T: Copy + PartialEq>(vertices: &[T], closed: bool) -> Vec<(T, T)> {
+    let mut segments: Vec<(T, T)> = vertices
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .filter(|(start, end)| start != end)
+        .collect();
+
+    if closed && vertices.len() > 2 {
+        let (first, last) = (vertices[0], vertices[vertices.len() - 1]);
+        if first != last {
+            segments.push((last, first));
+        }
+    }
+
+    segments
+}
+
+/// Iterator over the pixels of a 1px wide polyline.
+///
+/// Pixels shared by neighbouring segments, like the vertex at each joint, are only yielded once.
+#[derive(Clone, Debug)]
+pub struct PolylinePixels {
+    segments: std::vec::IntoIter<ivec2::LineSegment>,
+    draw_mode: LineDrawAlgo,
+    clip: Option<ivec2::Rectangle>,
+    closed: bool,
+    current: Option<LineIter>,
+    /// Pixels of the previous segment.
+    previous: HashSet<IVec2>,
+    /// Pixels of the current segment.
+    visited: HashSet<IVec2>,
+    /// Pixels of the first segment, closed polylines end on it.
+    first: Option<HashSet<IVec2>>,
+}
+
+impl PolylinePixels {
+    pub(crate) fn new(
+        vertices: &[IVec2],
+        closed: bool,
+        draw_mode: LineDrawAlgo,
+        clip: Option<ivec2::Rectangle>,
+    ) -> Self {
+        let segments: Vec<ivec2::LineSegment> = segments(vertices, closed)
+            .into_iter()
+            .map(|(start, end)| ivec2::LineSegment::new(start, end))
+            .collect();
+
+        Self {
+            segments: segments.into_iter(),
+            draw_mode,
+            clip,
+            closed,
+            current: None,
+            previous: HashSet::new(),
+            visited: HashSet::new(),
+            first: None,
+        }
+    }
+
+    fn next_segment(&mut self) -> Option<()> {
+        let segment = self.segments.next()?;
+
+        let visited = std::mem::take(&mut self.visited);
+        if self.first.is_none() && self.current.is_some() {
+            self.first = Some(visited.clone());
+        }
+        self.previous = visited;
+        self.current = Some(match self.clip {
+            Some(clip) => LineIter::new_from_segment_clipped(segment, self.draw_mode, &clip),
+            None => LineIter::new_from_segment(segment, self.draw_mode),
+        });
+
+        Some(())
+    }
+}
+
+impl Iterator for PolylinePixels {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(point) = self.current.as_mut().and_then(|line| line.next()) else {
+                self.next_segment()?;
+                continue;
+            };
+
+            let is_last = self.segments.len() == 0;
+            let seen = self.previous.contains(&point)
+                || (self.closed && is_last && self.first.as_ref().is_some_and(|first| first.contains(&point)));
+
+            if self.visited.insert(point) && !seen {
+                return Some(point);
+            }
+        }
+    }
+}
+
+/// A convex part of a stroked polyline.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum StrokePart {
+    Segment(ThickSegment),
+    /// Convex quad between the outer corners of two segments, triangles repeat a corner.
+    Wedge([Vec2; 4]),
+    Disc(Vec2, f32),
+}
+
+impl StrokePart {
+    fn contains(&self, point: Vec2) -> bool {
+        match self {
+            StrokePart::Segment(segment) => segment.contains(point),
+            StrokePart::Wedge(corners) => {
+                let mut sign = 0.0;
+                for i in 0..4 {
+                    let (a, b) = (corners[i], corners[(i + 1) % 4]);
+                    let side = (b - a).perp_dot(point - a);
+                    if side != 0.0 {
+                        if sign * side < 0.0 {
+                            return false;
+                        }
+                        sign = side;
+                    }
+                }
+                true
+            }
+            StrokePart::Disc(center, radius) => point.distance_squared(*center) < radius * radius,
+        }
+    }
+
+    /// Returns a column of row `y` that is covered if any column of the row is, see `convex_row`.
+    fn row_seed(&self, y: f32) -> f32 {
+        match self {
+            StrokePart::Segment(segment) => segment.row_seed(y),
+            StrokePart::Wedge(corners) => {
+                // the middle of the points where the edges cross the row
+                let (mut min, mut max) = (f32::INFINITY, f32::NEG_INFINITY);
+                for i in 0..4 {
+                    let (a, b) = (corners[i], corners[(i + 1) % 4]);
+                    if y < a.y.min(b.y) || y > a.y.max(b.y) {
+                        continue;
+                    }
+                    let (left, right) = if a.y == b.y {
+                        (a.x.min(b.x), a.x.max(b.x))
+                    } else {
+                        let x = a.x + (b.x - a.x) * (y - a.y) / (b.y - a.y);
+                        (x, x)
+                    };
+                    (min, max) = (min.min(left), max.max(right));
+                }
+                if min <= max {
+                    (min + max) / 2.0
+                } else {
+                    corners[0].x
+                }
+            }
+            StrokePart::Disc(center, _) => center.x,
+        }
+    }
+
+    fn bounds(&self) -> (Range<i32>, Range<i32>) {
+        let (min, max) = match self {
+            StrokePart::Segment(segment) => return segment.bounds(),
+            StrokePart::Wedge(corners) => corners[1..].iter().fold((corners[0], corners[0]), |(min, max), c| {
+                (min.min(*c), max.max(*c))
+            }),
+            StrokePart::Disc(center, radius) => (*center - *radius, *center + *radius),
+        };
+        let min = min.floor().as_ivec2();
+        let max = max.ceil().as_ivec2();
+
+        (min.y..max.y + 1, min.x..max.x + 1)
+    }
+}
+
+/// Iterator over the pixels of a polyline stroked with a width.
+///
+/// The segments, joins and caps overlap, their spans are merged per row so every covered pixel is
+/// yielded exactly once, in scanline order.
+#[derive(Clone, Debug)]
+pub struct PolylineStrokePixels {
+    parts: Vec<(StrokePart, Range<i32>, Range<i32>)>,
+    rows: Range<i32>,
+    spans: Vec<Range<i32>>,
+    pending: Vec<Scanline>,
+    current_scanline: Scanline,
+}
+
+impl PolylineStrokePixels {
+    pub(crate) fn new(vertices: &[Vec2], closed: bool, width: f32, join: LineJoin, cap: LineCap) -> Self {
+        let segments = segments(vertices, closed);
+        let half_width = width.max(0.0) / 2.0;
+        let mut parts = Vec::new();
+
+        for (start, end) in segments.iter() {
+            parts.push(StrokePart::Segment(ThickSegment::new(*start, *end, width, LineCap::Butt)));
+        }
+
+        // joints between consecutive segments, closed polylines also join the last to the first
+        let joints = if closed { segments.len() } else { segments.len().saturating_sub(1) };
+        for i in 0..joints {
+            let (before, after) = (segments[i], segments[(i + 1) % segments.len()]);
+            if let Some(part) = join_part(before, after, half_width, join) {
+                parts.push(part);
+            }
+        }
+
+        if !closed {
+            if let (Some(first), Some(last)) = (segments.first(), segments.last()) {
+                parts.extend(cap_part(first.1, first.0, width, cap));
+                parts.extend(cap_part(last.0, last.1, width, cap));
+            }
+        }
+
+        let parts: Vec<(StrokePart, Range<i32>, Range<i32>)> = parts
+            .into_iter()
+            .map(|part| {
+                let (rows, columns) = part.bounds();
+                (part, rows, columns)
+            })
+            .collect();
+
+        let rows = parts
+            .iter()
+            .map(|(_, rows, _)| rows.clone())
+            .reduce(|a, b| a.start.min(b.start)..a.end.max(b.end))
+            .unwrap_or(0..0);
+
+        Self {
+            parts,
+            rows,
+            spans: Vec::new(),
+            pending: Vec::new(),
+            current_scanline: Scanline::new_empty(0),
+        }
+    }
+
+    /// Fills `pending` with the merged spans of all parts on row `y`.
+    fn fill_row(&mut self, y: i32) {
+        self.spans.clear();
+        for (part, rows, columns) in self.parts.iter() {
+            if rows.contains(&y) {
+                let span = convex_row(y, columns.clone(), part.row_seed(y as f32), |point| part.contains(point));
+                if !span.is_empty() {
+                    self.spans.push(span);
+                }
+            }
+        }
+        self.spans.sort_by_key(|span| span.start);
+
+        let mut merged: Option<Range<i32>> = None;
+        for span in self.spans.drain(..) {
+            merged = match merged {
+                Some(current) if span.start <= current.end => Some(current.start..current.end.max(span.end)),
+                Some(current) => {
+                    self.pending.push(Scanline::new(y, current, false));
+                    Some(span)
+                }
+                None => Some(span),
+            };
+        }
+        if let Some(current) = merged {
+            self.pending.push(Scanline::new(y, current, false));
+        }
+
+        self.pending.reverse();
+    }
+}
+
+/// Returns the part filling the outer corner between two segments.
+fn join_part(before: (Vec2, Vec2), after: (Vec2, Vec2), half_width: f32, join: LineJoin) -> Option<StrokePart> {
+    let vertex = before.1;
+    let direction_before = (before.1 - before.0).normalize();
+    let direction_after = (after.1 - after.0).normalize();
+
+    if join == LineJoin::Round {
+        return Some(StrokePart::Disc(vertex, half_width));
+    }
+
+    let turn = direction_before.perp_dot(direction_after);
+    if turn == 0.0 {
+        return None;
+    }
+
+    // the outer side of the corner is opposite to the direction of the turn
+    let side = -turn.signum();
+    let normal_before = direction_before.perp() * side;
+    let normal_after = direction_after.perp() * side;
+    let corner_before = vertex + normal_before * half_width;
+    let corner_after = vertex + normal_after * half_width;
+
+    let miter = (normal_before + normal_after).normalize_or_zero();
+    let miter_ratio = 1.0 / miter.dot(normal_before);
+
+    if join == LineJoin::Miter && miter_ratio.is_finite() && miter_ratio <= MITER_LIMIT {
+        let tip = vertex + miter * half_width * miter_ratio;
+        Some(StrokePart::Wedge([vertex, corner_before, tip, corner_after]))
+    } else {
+        Some(StrokePart::Wedge([vertex, corner_before, corner_after, corner_after]))
+    }
+}
+
+/// Returns the part capping the end of a polyline at `end`, coming from `from`.
+fn cap_part(from: Vec2, end: Vec2, width: f32, cap: LineCap) -> Option<StrokePart> {
+    let direction = (end - from).normalize();
+    let half_width = width.max(0.0) / 2.0;
+
+    match cap {
+        LineCap::Butt => None,
+        LineCap::Square => Some(StrokePart::Segment(ThickSegment::new(
+            end,
+            end + direction * half_width,
+            width,
+            LineCap::Butt,
+        ))),
+        LineCap::Round => Some(StrokePart::Disc(end, half_width)),
+    }
+}
+
+impl Iterator for PolylineStrokePixels {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.current_scanline.next() {
+                return Some(point);
+            }
+            while self.pending.is_empty() {
+                let y = self.rows.next()?;
+                self.fill_row(y);
+            }
+            self.current_scanline = self.pending.pop()?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn unique(points: &[IVec2]) -> bool {
+        points.iter().collect::<HashSet<_>>().len() == points.len()
+    }
+
+    #[test]
+    fn test_thin_joints() {
+        let vertices = [IVec2::new(0, 0), IVec2::new(3, 0), IVec2::new(3, 3)];
+        let points: Vec<IVec2> = PolylinePixels::new(&vertices, false, LineDrawAlgo::Bresenham, None).collect();
+
+        assert_eq!(
+            points,
+            vec![
+                IVec2::new(0, 0),
+                IVec2::new(1, 0),
+                IVec2::new(2, 0),
+                IVec2::new(3, 0),
+                IVec2::new(3, 1),
+                IVec2::new(3, 2),
+                IVec2::new(3, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_thin_closed() {
+        let vertices = [IVec2::new(0, 0), IVec2::new(4, 0), IVec2::new(4, 4), IVec2::new(0, 4)];
+        for draw_mode in [LineDrawAlgo::Bresenham, LineDrawAlgo::WalkGrid] {
+            let points: Vec<IVec2> = PolylinePixels::new(&vertices, true, draw_mode, None).collect();
+
+            assert_eq!(points.len(), 16);
+            assert!(unique(&points));
+        }
+    }
+
+    #[test]
+    fn test_thin_sharp_corner() {
+        let vertices = [IVec2::new(0, 0), IVec2::new(10, 1), IVec2::new(0, 2)];
+        for draw_mode in [LineDrawAlgo::Bresenham, LineDrawAlgo::WalkGrid] {
+            let points: Vec<IVec2> = PolylinePixels::new(&vertices, false, draw_mode, None).collect();
+            assert!(unique(&points));
+        }
+    }
+
+    #[test]
+    fn test_stroke_no_duplicates() {
+        let vertices = [
+            Vec2::new(2.0, 2.0),
+            Vec2::new(20.0, 4.0),
+            Vec2::new(6.0, 12.0),
+            Vec2::new(18.0, 18.0),
+        ];
+        for join in [LineJoin::Miter, LineJoin::Bevel, LineJoin::Round] {
+            for closed in [false, true] {
+                let points: Vec<IVec2> =
+                    PolylineStrokePixels::new(&vertices, closed, 5.0, join, LineCap::Round).collect();
+                assert!(unique(&points));
+            }
+        }
+    }
+
+    #[test]
+    fn test_stroke_joins() {
+        let vertices = [Vec2::new(2.0, 10.0), Vec2::new(10.0, 10.0), Vec2::new(10.0, 2.0)];
+        let stroke = |join| -> HashSet<IVec2> {
+            PolylineStrokePixels::new(&vertices, false, 6.0, join, LineCap::Butt).collect()
+        };
+        let (miter, bevel, round) = (stroke(LineJoin::Miter), stroke(LineJoin::Bevel), stroke(LineJoin::Round));
+
+        // the outer corner of the right angle is cut off by the bevel join
+        assert!(miter.contains(&IVec2::new(12, 12)));
+        assert!(round.contains(&IVec2::new(12, 12)));
+        assert!(!bevel.contains(&IVec2::new(12, 12)));
+        assert!(bevel.is_subset(&miter));
+        assert!(round.is_subset(&miter));
+    }
+
+    #[test]
+    fn test_stroke_matches_scan() {
+        let vertices = [
+            Vec2::new(2.0, 2.0),
+            Vec2::new(20.0, 4.0),
+            Vec2::new(6.0, 12.0),
+            Vec2::new(18.5, 18.0),
+            Vec2::new(17.0, 1.5),
+        ];
+        for join in [LineJoin::Miter, LineJoin::Bevel, LineJoin::Round] {
+            for cap in [LineCap::Butt, LineCap::Square, LineCap::Round] {
+                let stroke = PolylineStrokePixels::new(&vertices, false, 4.5, join, cap);
+                let covered: HashSet<IVec2> = stroke
+                    .parts
+                    .iter()
+                    .flat_map(|(part, rows, columns)| {
+                        rows.clone()
+                            .flat_map(move |y| columns.clone().map(move |x| IVec2::new(x, y)))
+                            .filter(|p| part.contains(p.as_vec2()))
+                    })
+                    .collect();
+
+                assert_eq!(stroke.collect::<HashSet<IVec2>>(), covered, "{:?} {:?}", join, cap);
+            }
+        }
+    }
+}
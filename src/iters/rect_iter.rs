@@ -2,47 +2,120 @@ use std::ops::Range;
 
 use glam::IVec2;
 
+use super::common::Scanline;
 use crate::ivec2;
 
 
 /// Iterator over all IVec2s inside the rectangle.
+///
+/// The pixels are yielded row by row. Their number is known up front, so the iterator also runs
+/// backwards and reports its exact length.
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct RectanglePixels {
     outline: bool,
+    /// Visible columns and rows.
     x: Range<i32>,
     y: Range<i32>,
-    x_start: i32,
-    y_start: i32,
+    /// Number of pixels in the first and last visible row.
+    head: i64,
+    tail: i64,
+    /// Number of pixels in each row between them.
+    middle_width: i64,
+    /// Visible left and right edge of the outline, yielded in the rows between the first and
+    /// last row.
+    edges: [i32; 2],
+    /// Position of the remaining pixels in row order.
+    index: Range<i64>,
 }
 
 impl RectanglePixels {
     pub(crate) fn new(rectangle: &ivec2::Rectangle, outline: bool) -> Self {
-        // eprintln!("RectanglePixels rect: {:?}, outline: {:?}", rectangle, outline);
+        Self::new_clipped(rectangle, outline, rectangle)
+    }
+
+    /// Creates an iterator over the pixels of `rectangle` that lie inside `clip`.
+    ///
+    /// The outline is the outline of the unclipped rectangle, clipping doesn't add pixels along
+    /// the edges of `clip`.
+    pub(crate) fn new_clipped(rectangle: &ivec2::Rectangle, outline: bool, clip: &ivec2::Rectangle) -> Self {
         // Return `Self::empty` for all zero sized rectangles.
-        // The iterator would behave correctly without this check, but would loop unnecessarily for
-        // rectangles with zero width.
         if rectangle.is_zero_sized() {
             return Self::empty();
         }
+        let Some(visible) = rectangle.intersection(clip) else {
+            return Self::empty();
+        };
 
-        let x = rectangle.columns();
-        let y = rectangle.rows();
-        let x_start = x.start;
-        let y_start = y.start;
+        let x = visible.columns();
+        let y = visible.rows();
+        let (width, height) = (x.len() as i64, y.len() as i64);
+        if width == 0 || height == 0 {
+            return Self::empty();
+        }
+
+        // the top and bottom row of the outline are full rows, the rows between them only
+        // contain the left and right edge
+        let full_row = |row| !outline || row == rectangle.tl.y || row == rectangle.br.y - 1;
+        let head = if full_row(y.start) { width } else { 0 };
+        let tail = if height > 1 && full_row(y.end - 1) { width } else { 0 };
+
+        let mut edges = [0; 2];
+        let mut middle_width = width;
+        if outline {
+            middle_width = 0;
+            for edge in [rectangle.tl.x, rectangle.br.x - 1] {
+                if x.contains(&edge) && (middle_width == 0 || edges[0] != edge) {
+                    edges[middle_width as usize] = edge;
+                    middle_width += 1;
+                }
+            }
+        }
+        let middle_rows = height - (head > 0) as i64 - (tail > 0) as i64;
 
-        Self { x, y, x_start, y_start, outline }
+        Self {
+            outline,
+            x,
+            y,
+            head,
+            tail,
+            middle_width,
+            edges,
+            index: 0..head + middle_rows * middle_width + tail,
+        }
     }
 
     /// Create a points iterator that returns no items.
     pub const fn empty() -> Self {
         Self {
+            outline: false,
             x: 0..0,
             y: 0..0,
-            x_start: 0,
-            y_start: 0,
-            outline: false,
+            head: 0,
+            tail: 0,
+            middle_width: 0,
+            edges: [0; 2],
+            index: 0..0,
         }
     }
+
+    /// Returns the pixel at position `k` in row order.
+    fn point(&self, k: i64) -> IVec2 {
+        if k < self.head {
+            return IVec2::new(self.x.start + k as i32, self.y.start);
+        }
+
+        let k = k - self.head;
+        let middle_rows = self.y.len() as i64 - (self.head > 0) as i64 - (self.tail > 0) as i64;
+        let middle = middle_rows * self.middle_width;
+        if k < middle {
+            let y = self.y.start + (self.head > 0) as i32 + (k / self.middle_width) as i32;
+            let column = k % self.middle_width;
+            let x = if self.outline { self.edges[column as usize] } else { self.x.start + column as i32 };
+            return IVec2::new(x, y);
+        }
+
+        IVec2::new(self.x.start + (k - middle) as i32, self.y.end - 1)
+    }
 }
 
 impl Iterator for RectanglePixels {
@@ -50,31 +123,227 @@ impl Iterator for RectanglePixels {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        while !self.y.is_empty() {
-            if let Some(x) = self.x.next() {
-                if !self.outline || self.y.start == self.y_start || self.y.start == self.y.end - 1 {
-                    return Some(IVec2::new(x, self.y.start));
-                } else if self.outline {
-                    if x == self.x_start || x == self.x.end - 1 {
-                        if x == self.x_start {
-                            self.x.start = self.x.end - 1;
-                        }
-                        return Some(IVec2::new(x, self.y.start));
-                    } 
-                }
-            }
-            // eprintln!("no x: {}", self.y.start);
+        self.index.next().map(|k| self.point(k))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.index.end - self.index.start) as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for RectanglePixels {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.index.next_back().map(|k| self.point(k))
+    }
+}
+
+impl ExactSizeIterator for RectanglePixels {}
+
+/// Iterator over the outline of a rectangle in path order.
+///
+/// Walks the outline clockwise on screen starting at the top left pixel: along the top edge to
+/// the right, down the right edge, back along the bottom edge and up the left edge. Each pixel is
+/// yielded once, also for rectangles that are only one pixel wide or high.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct RectanglePerimeter {
+    tl: IVec2,
+    size: IVec2,
+    /// Position of the next pixel along the outline.
+    index: Range<i32>,
+}
+
+impl RectanglePerimeter {
+    pub(crate) fn new(rectangle: &ivec2::Rectangle) -> Self {
+        let size = rectangle.size();
+        let len = match (size.x, size.y) {
+            (w, h) if w <= 0 || h <= 0 => 0,
+            (1, h) => h,
+            (w, 1) => w,
+            (w, h) => 2 * (w + h) - 4,
+        };
+
+        Self {
+            tl: rectangle.tl,
+            size,
+            index: 0..len,
+        }
+    }
+
+    /// Returns the pixel at position `k` along the outline.
+    fn point(&self, k: i32) -> IVec2 {
+        let (w, h) = (self.size.x, self.size.y);
+        let offset = if k < w {
+            IVec2::new(k, 0)
+        } else if k < w + h - 1 {
+            IVec2::new(w - 1, k - w + 1)
+        } else if k < 2 * w + h - 2 {
+            IVec2::new(2 * w + h - 3 - k, h - 1)
+        } else {
+            IVec2::new(0, 2 * (w + h) - 4 - k)
+        };
+
+        self.tl + offset
+    }
+}
+
+impl Iterator for RectanglePerimeter {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.index.next().map(|k| self.point(k))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.index.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for RectanglePerimeter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.index.next_back().map(|k| self.point(k))
+    }
+}
+
+impl ExactSizeIterator for RectanglePerimeter {}
+
+/// Iterator over the rows of a rectangle, yielding each row with its range of columns.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct RectangleSpans {
+    rows: Range<i32>,
+    columns: Range<i32>,
+}
+
+impl RectangleSpans {
+    pub(crate) fn new(rectangle: &ivec2::Rectangle) -> Self {
+        if rectangle.is_zero_sized() {
+            return Self { rows: 0..0, columns: 0..0 };
+        }
+
+        Self {
+            rows: rectangle.rows(),
+            columns: rectangle.columns(),
+        }
+    }
+}
+
+impl Iterator for RectangleSpans {
+    type Item = (i32, Range<i32>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rows.next().map(|y| (y, self.columns.clone()))
+    }
+}
+
+/// Where the border of a rectangle lies relative to its edges.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum BorderAlignment {
+    /// The border grows inward and stays inside the rectangle.
+    #[default]
+    Inside,
+    /// The border grows outward and surrounds the rectangle.
+    Outside,
+    /// The border straddles the edges. If the thickness is odd, the extra pixel is on the inside.
+    Center,
+}
+
+/// Iterator over the rows of a rectangle border, yielding each row with a range of columns.
+///
+/// Rows crossing the top or bottom of the border yield one span. Rows between them yield a
+/// left and a right span. Every pixel is covered by exactly one span.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct RectangleBorderSpans {
+    rows: Range<i32>,
+    columns: Range<i32>,
+    /// The hole inside the border. It is empty if the border fills the rectangle.
+    inner: ivec2::Rectangle,
+    /// Right span of the current row.
+    next_span: Option<(i32, Range<i32>)>,
+}
 
-            self.y.next();
-            self.x.start = self.x_start;
+impl RectangleBorderSpans {
+    pub(crate) fn new(rectangle: &ivec2::Rectangle, thickness: u32, alignment: BorderAlignment) -> Self {
+        if rectangle.is_zero_sized() || thickness == 0 {
+            return Self {
+                rows: 0..0,
+                columns: 0..0,
+                inner: ivec2::Rectangle::default(),
+                next_span: None,
+            };
         }
 
-        None
+        let thickness = thickness.min(i32::MAX as u32 / 4) as i32;
+        let outward = match alignment {
+            BorderAlignment::Inside => 0,
+            BorderAlignment::Outside => thickness,
+            BorderAlignment::Center => thickness / 2,
+        };
+        let tl = rectangle.tl - IVec2::splat(outward);
+        let br = rectangle.br + IVec2::splat(outward);
+        let inner_tl = tl + IVec2::splat(thickness);
+        let inner_br = br - IVec2::splat(thickness);
+        let inner = if inner_tl.cmplt(inner_br).all() {
+            ivec2::Rectangle::new(inner_tl, inner_br)
+        } else {
+            ivec2::Rectangle::default()
+        };
+
+        Self {
+            rows: tl.y..br.y,
+            columns: tl.x..br.x,
+            inner,
+            next_span: None,
+        }
     }
 }
 
+impl Iterator for RectangleBorderSpans {
+    type Item = (i32, Range<i32>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(span) = self.next_span.take() {
+            return Some(span);
+        }
+
+        let y = self.rows.next()?;
+        if self.inner.is_zero_sized() || !self.inner.rows().contains(&y) {
+            return Some((y, self.columns.clone()));
+        }
 
+        self.next_span = Some((y, self.inner.br.x..self.columns.end));
+        Some((y, self.columns.start..self.inner.tl.x))
+    }
+}
 
+/// Iterator over the pixels of a rectangle border, row by row.
+#[derive(Clone, Debug)]
+pub struct RectangleBorderPixels {
+    spans: RectangleBorderSpans,
+    current_scanline: Scanline,
+}
+
+impl RectangleBorderPixels {
+    pub(crate) fn new(rectangle: &ivec2::Rectangle, thickness: u32, alignment: BorderAlignment) -> Self {
+        Self {
+            spans: RectangleBorderSpans::new(rectangle, thickness, alignment),
+            current_scanline: Scanline::new_empty(0),
+        }
+    }
+}
+
+impl Iterator for RectangleBorderPixels {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.current_scanline.next() {
+                return Some(point);
+            }
+            let (y, columns) = self.spans.next()?;
+            self.current_scanline = Scanline::new(y, columns, false);
+        }
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -147,6 +416,152 @@ mod test {
     }
 
 
+    #[test]
+    fn rectangle_spans() {
+        let rect = ivec2::Rectangle::new(IVec2::new(1, 2), IVec2::new(4, 5));
+        let spans: Vec<(i32, Range<i32>)> = RectangleSpans::new(&rect).collect();
+        assert_eq!(spans, vec![(2, 1..4), (3, 1..4), (4, 1..4)]);
+
+        let empty = ivec2::Rectangle::new(IVec2::new(1, 2), IVec2::new(1, 5));
+        assert_eq!(RectangleSpans::new(&empty).count(), 0);
+    }
+
+    #[test]
+    fn rectangle_perimeter() {
+        let rect = ivec2::Rectangle::new(IVec2::new(1, 1), IVec2::new(4, 4));
+        let pixels: Vec<IVec2> = RectanglePerimeter::new(&rect).collect();
+        assert_eq!(pixels, vec![
+            IVec2::new(1, 1),
+            IVec2::new(2, 1),
+            IVec2::new(3, 1),
+            IVec2::new(3, 2),
+            IVec2::new(3, 3),
+            IVec2::new(2, 3),
+            IVec2::new(1, 3),
+            IVec2::new(1, 2),
+        ]);
+
+        for size in [IVec2::new(1, 1), IVec2::new(1, 5), IVec2::new(5, 1), IVec2::new(2, 2), IVec2::new(7, 3)] {
+            let rect = ivec2::Rectangle::new(IVec2::new(-2, 3), IVec2::new(-2, 3) + size);
+            let mut perimeter: Vec<IVec2> = RectanglePerimeter::new(&rect).collect();
+            let mut outline: Vec<IVec2> = RectanglePixels::new(&rect, true).collect();
+            perimeter.sort_by_key(|p| (p.y, p.x));
+            outline.sort_by_key(|p| (p.y, p.x));
+            outline.dedup();
+            assert_eq!(perimeter, outline, "{:?}", size);
+        }
+
+        let empty = ivec2::Rectangle::new(IVec2::new(1, 2), IVec2::new(1, 5));
+        assert_eq!(RectanglePerimeter::new(&empty).count(), 0);
+    }
+
+    #[test]
+    fn border_spans() {
+        let rect = ivec2::Rectangle::new(IVec2::new(0, 0), IVec2::new(6, 5));
+        let spans: Vec<(i32, Range<i32>)> = RectangleBorderSpans::new(&rect, 2, BorderAlignment::Inside).collect();
+        assert_eq!(spans, vec![(0, 0..6), (1, 0..6), (2, 0..2), (2, 4..6), (3, 0..6), (4, 0..6)]);
+
+        let spans: Vec<(i32, Range<i32>)> = RectangleBorderSpans::new(&rect, 1, BorderAlignment::Outside).collect();
+        assert_eq!(spans.first(), Some(&(-1, -1..7)));
+        assert_eq!(spans[1..3], [(0, -1..0), (0, 6..7)]);
+        assert_eq!(spans.last(), Some(&(5, -1..7)));
+
+        // a thickness of 1 inside is the 1px outline
+        let mut pixels: Vec<IVec2> = RectangleBorderPixels::new(&rect, 1, BorderAlignment::Inside).collect();
+        pixels.sort_by_key(|p| (p.y, p.x));
+        assert_eq!(pixels, RectanglePixels::new(&rect, true).collect::<Vec<_>>());
+
+        assert_eq!(RectangleBorderSpans::new(&rect, 0, BorderAlignment::Center).count(), 0);
+    }
+
+    #[test]
+    fn border_alignment() {
+        let rect = ivec2::Rectangle::new(IVec2::new(10, 10), IVec2::new(20, 16));
+        let r = |x0, y0, x1, y1| ivec2::Rectangle::new(IVec2::new(x0, y0), IVec2::new(x1, y1));
+        // thickness, alignment, the outer edge of the border and the hole inside of it
+        let cases = [
+            (2, BorderAlignment::Inside, r(10, 10, 20, 16), Some(r(12, 12, 18, 14))),
+            (3, BorderAlignment::Inside, r(10, 10, 20, 16), None),
+            (2, BorderAlignment::Outside, r(8, 8, 22, 18), Some(rect)),
+            (5, BorderAlignment::Outside, r(5, 5, 25, 21), Some(rect)),
+            (1, BorderAlignment::Center, r(10, 10, 20, 16), Some(r(11, 11, 19, 15))),
+            (2, BorderAlignment::Center, r(9, 9, 21, 17), Some(r(11, 11, 19, 15))),
+            // the extra pixel of an odd thickness goes inside
+            (3, BorderAlignment::Center, r(9, 9, 21, 17), Some(r(12, 12, 18, 14))),
+        ];
+
+        for (thickness, alignment, outer, hole) in cases {
+            let in_hole = |p: &IVec2| hole.is_some_and(|hole| hole.columns().contains(&p.x) && hole.rows().contains(&p.y));
+            let expected: Vec<IVec2> = RectanglePixels::new(&outer, false).filter(|p| !in_hole(p)).collect();
+
+            let pixels: Vec<IVec2> = RectangleBorderPixels::new(&rect, thickness, alignment).collect();
+            assert_eq!(pixels, expected, "{} {:?}", thickness, alignment);
+        }
+    }
+
+    #[test]
+    fn reversed_pixels() {
+        let rect = ivec2::Rectangle::new(IVec2::new(-2, -2), IVec2::new(5, 4));
+        let clips = [
+            rect,
+            ivec2::Rectangle::new(IVec2::new(0, 0), IVec2::new(10, 10)),
+            ivec2::Rectangle::new(IVec2::new(-5, 3), IVec2::new(10, 10)),
+            ivec2::Rectangle::new(IVec2::new(-1, -1), IVec2::new(4, 3)),
+        ];
+
+        for clip in clips {
+            for outline in [false, true] {
+                let pixels: Vec<IVec2> = RectanglePixels::new_clipped(&rect, outline, &clip).collect();
+                let expected: Vec<IVec2> = clip
+                    .rows()
+                    .flat_map(|y| clip.columns().map(move |x| IVec2::new(x, y)))
+                    .filter(|p| rect.columns().contains(&p.x) && rect.rows().contains(&p.y))
+                    .filter(|p| !outline || p.x == -2 || p.x == 4 || p.y == -2 || p.y == 3)
+                    .collect();
+                assert_eq!(pixels, expected);
+
+                let mut reversed: Vec<IVec2> = RectanglePixels::new_clipped(&rect, outline, &clip).rev().collect();
+                reversed.reverse();
+                assert_eq!(reversed, pixels);
+
+                let mut iter = RectanglePixels::new_clipped(&rect, outline, &clip);
+                for len in (0..=pixels.len()).rev() {
+                    assert_eq!(iter.len(), len);
+                    assert_eq!(iter.size_hint(), (len, Some(len)));
+                    if len % 2 == 0 { iter.next() } else { iter.next_back() };
+                }
+            }
+        }
+
+        // the bottom row of the outline is a full row even if it is the only visible row
+        let clip = ivec2::Rectangle::new(IVec2::new(0, 3), IVec2::new(10, 10));
+        assert_eq!(RectanglePixels::new_clipped(&rect, true, &clip).count(), 5);
+    }
+
+    #[test]
+    fn clipped_pixels() {
+        let rect = ivec2::Rectangle::new(IVec2::new(-2, -2), IVec2::new(3, 4));
+        let clip = ivec2::Rectangle::new(IVec2::new(0, 0), IVec2::new(10, 10));
+
+        for outline in [false, true] {
+            let expected: Vec<IVec2> = RectanglePixels::new(&rect, outline)
+                .filter(|p| p.cmpge(clip.tl).all() && p.cmplt(clip.br).all())
+                .collect();
+            let pixels: Vec<IVec2> = RectanglePixels::new_clipped(&rect, outline, &clip).collect();
+            assert_eq!(pixels, expected);
+        }
+
+        let outline: Vec<IVec2> = RectanglePixels::new_clipped(&rect, true, &clip).collect();
+        assert_eq!(outline, vec![
+            IVec2::new(2, 0),
+            IVec2::new(2, 1),
+            IVec2::new(2, 2),
+            IVec2::new(0, 3),
+            IVec2::new(1, 3),
+            IVec2::new(2, 3),
+        ]);
+    }
+
     #[test]
     fn rectangle_outline_pixels() {
         let rect = ivec2::Rectangle::new(IVec2::new(0, 0), IVec2::new(4, 4));
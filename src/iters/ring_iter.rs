@@ -0,0 +1,171 @@
+use std::ops::Range;
+
+use crate::ivec2;
+use glam::IVec2;
+
+use super::common::Scanline;
+
+/// Iterator over the pixels of a ring between two circles with the same center.
+///
+/// The ring contains the pixels of the outer circle that are not part of the inner circle, using
+/// the same center and thresholds as `CirclePoints`. It lines up exactly with filled circles and
+/// has no gaps, even when it is only one pixel thick.
+#[derive(Clone, Debug)]
+pub struct RingPixels {
+    rows: Range<i32>,
+    columns: Range<i32>,
+    center_2x: IVec2,
+    outer_threshold: i32,
+    inner_threshold: i32,
+    limits: Option<ivec2::Rectangle>,
+    current_scanline: Scanline,
+    /// Right half of the current row.
+    next_scanline: Option<Scanline>,
+}
+
+impl RingPixels {
+    pub(crate) fn new(circle: &ivec2::Circle, inner_radius: i32) -> Self {
+        let bounding_box = circle.bounds();
+        let inner = ivec2::Circle::new(circle.pos, inner_radius.clamp(0, circle.radius));
+
+        Self {
+            rows: bounding_box.rows_limited(&circle.limits),
+            columns: bounding_box.columns(),
+            center_2x: circle.center_2x(),
+            outer_threshold: circle.threshold(),
+            inner_threshold: inner.threshold(),
+            limits: circle.limits,
+            current_scanline: Scanline::new_empty(0),
+            next_scanline: None,
+        }
+    }
+
+    /// Returns the first column of row `y` in `columns` that is inside `threshold`.
+    fn first_inside(&self, y: i32, columns: Range<i32>, threshold: i32) -> Option<i32> {
+        columns.into_iter().find(|x| {
+            let delta = IVec2::new(*x, y) * 2 - self.center_2x;
+            delta.length_squared() < threshold
+        })
+    }
+
+    fn scanline(&self, y: i32, columns: Range<i32>) -> Scanline {
+        match self.limits {
+            Some(limits) => Scanline::new_clipped(y, columns, false, &limits.columns()),
+            None => Scanline::new(y, columns, false),
+        }
+    }
+
+    /// Sets up the scanlines of row `y`, the ring is split into a left and right part where it
+    /// crosses the inner circle.
+    fn start_row(&mut self, y: i32) {
+        self.next_scanline = None;
+        // both circles are symmetric around the center of the columns
+        let mirror = |x: i32| self.columns.end - (x - self.columns.start);
+
+        let Some(start) = self.first_inside(y, self.columns.clone(), self.outer_threshold) else {
+            self.current_scanline = Scanline::new_empty(y);
+            return;
+        };
+        let end = mirror(start);
+
+        match self.first_inside(y, start..end, self.inner_threshold) {
+            Some(inner_start) => {
+                self.current_scanline = self.scanline(y, start..inner_start);
+                self.next_scanline = Some(self.scanline(y, mirror(inner_start)..end));
+            }
+            None => self.current_scanline = self.scanline(y, start..end),
+        }
+    }
+}
+
+impl Iterator for RingPixels {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.current_scanline.next() {
+                return Some(point);
+            }
+            match self.next_scanline.take() {
+                Some(scanline) => self.current_scanline = scanline,
+                None => {
+                    let y = self.rows.next()?;
+                    self.start_row(y);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn ring(radius: i32, inner_radius: i32) -> HashSet<IVec2> {
+        let circle = ivec2::Circle::new(IVec2::new(20, 20), radius);
+        RingPixels::new(&circle, inner_radius).collect()
+    }
+
+    fn disc(radius: i32) -> HashSet<IVec2> {
+        ivec2::Circle::new(IVec2::new(20, 20), radius).pixel_iter(false).collect()
+    }
+
+    #[test]
+    fn test_matches_circles() {
+        for radius in 1..12 {
+            for inner_radius in 0..=radius {
+                let expected: HashSet<IVec2> = disc(radius).difference(&disc(inner_radius)).copied().collect();
+                assert_eq!(ring(radius, inner_radius), expected, "{} {}", radius, inner_radius);
+            }
+        }
+    }
+
+    /// A one pixel thick ring is 8-connected, and its inside can't be reached from the outside
+    /// without crossing it.
+    #[test]
+    fn test_no_gaps() {
+        for radius in 2..16 {
+            let ring = ring(radius, radius - 1);
+
+            let start = *ring.iter().next().unwrap();
+            let mut connected = HashSet::from([start]);
+            let mut stack = vec![start];
+            while let Some(point) = stack.pop() {
+                for y in -1..=1 {
+                    for x in -1..=1 {
+                        let neighbour = point + IVec2::new(x, y);
+                        if ring.contains(&neighbour) && connected.insert(neighbour) {
+                            stack.push(neighbour);
+                        }
+                    }
+                }
+            }
+            assert_eq!(connected, ring);
+
+            let mut seen = HashSet::new();
+            let mut stack = vec![IVec2::new(20, 20)];
+
+            while let Some(point) = stack.pop() {
+                if ring.contains(&point) || !seen.insert(point) {
+                    continue;
+                }
+                assert!((point - IVec2::new(20, 20)).abs().max_element() <= radius, "gap in ring {}", radius);
+                stack.extend([IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y].map(|d| point + d));
+            }
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        let limits = ivec2::Rectangle::new(IVec2::new(0, 0), IVec2::new(20, 20));
+        let circle = ivec2::Circle::new_with_limits(IVec2::new(20, 20), 6, limits);
+        let points: Vec<IVec2> = RingPixels::new(&circle, 3).collect();
+
+        let expected: Vec<IVec2> = RingPixels::new(&ivec2::Circle::new(IVec2::new(20, 20), 6), 3)
+            .filter(|p| p.cmplt(IVec2::splat(20)).all())
+            .collect();
+        assert_eq!(points, expected);
+    }
+}
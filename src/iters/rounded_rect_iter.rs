@@ -0,0 +1,254 @@
+use std::ops::Range;
+
+use glam::IVec2;
+
+use super::common::{outline_runs, Scanline};
+use crate::ivec2;
+
+/// Rows of a rectangle with rounded corners.
+///
+/// Each corner is a quarter of an `ivec2::Circle` with the corner radius, placed so that it
+/// touches the two edges of the rectangle.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Corners {
+    rect: ivec2::Rectangle,
+    radius: i32,
+    threshold: i32,
+}
+
+impl Corners {
+    fn new(rect: &ivec2::Rectangle, radius: i32) -> Self {
+        let size = rect.size();
+        let radius = radius.clamp(0, size.x.min(size.y).max(0) / 2);
+
+        Self {
+            rect: *rect,
+            radius,
+            threshold: ivec2::Circle::new(IVec2::ZERO, radius).threshold(),
+        }
+    }
+
+    /// Returns the number of pixels the corners cut off both ends of row `y`.
+    ///
+    /// Rows outside of the rectangle have no pixels, their inset is `None`.
+    fn inset(&self, y: i32) -> Option<i32> {
+        if !self.rect.rows().contains(&y) {
+            return None;
+        }
+        // distance in rows from the nearest of the top and bottom edges
+        let row = (y - self.rect.tl.y).min(self.rect.br.y - 1 - y);
+        if row >= self.radius {
+            return Some(0);
+        }
+
+        // doubled offsets from the center of the corner circle, the same as for `CirclePoints`
+        let dy = 2 * row + 1 - 2 * self.radius;
+        let inset = (0..self.radius)
+            .find(|x| {
+                let dx = 2 * x + 1 - 2 * self.radius;
+                dx * dx + dy * dy < self.threshold
+            })
+            .unwrap_or(self.radius);
+
+        Some(inset)
+    }
+
+    /// Returns the filled columns of row `y`.
+    fn columns(&self, y: i32) -> Range<i32> {
+        match self.inset(y) {
+            Some(inset) => self.rect.tl.x + inset..self.rect.br.x - inset,
+            None => 0..0,
+        }
+    }
+
+    fn contains(&self, point: IVec2) -> bool {
+        self.columns(point.y).contains(&point.x)
+    }
+}
+
+/// Returns `true` if the pixel at `point` is filled by the rounded rectangle.
+pub(crate) fn contains(rect: &ivec2::Rectangle, radius: i32, point: IVec2) -> bool {
+    Corners::new(rect, radius).contains(point)
+}
+
+/// Iterator over the pixels of a rectangle with rounded corners.
+///
+/// The corners have the same pixels as a filled `ivec2::Circle` with the corner radius. The
+/// outline consists of the filled pixels next to a pixel that is not filled, so it has no gaps.
+#[derive(Clone, Debug)]
+pub struct RoundedRectanglePixels {
+    corners: Corners,
+    outline: bool,
+    rows: Range<i32>,
+    clip: Option<Range<i32>>,
+    current_scanline: Scanline,
+    /// Right side of the outline of the current row.
+    next_scanline: Option<Scanline>,
+}
+
+impl RoundedRectanglePixels {
+    pub(crate) fn new(
+        rect: &ivec2::Rectangle,
+        radius: i32,
+        outline: bool,
+        clip: Option<ivec2::Rectangle>,
+    ) -> Self {
+        Self {
+            corners: Corners::new(rect, radius),
+            outline,
+            rows: rect.rows_limited(&clip),
+            clip: clip.map(|clip| clip.columns()),
+            current_scanline: Scanline::new_empty(0),
+            next_scanline: None,
+        }
+    }
+
+    fn scanline(&self, y: i32, columns: Range<i32>) -> Scanline {
+        match &self.clip {
+            Some(clip) => Scanline::new_clipped(y, columns, false, clip),
+            None => Scanline::new(y, columns, false),
+        }
+    }
+}
+
+impl Iterator for RoundedRectanglePixels {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.current_scanline.next() {
+                return Some(point);
+            }
+            if let Some(scanline) = self.next_scanline.take() {
+                self.current_scanline = scanline;
+                continue;
+            }
+
+            let y = self.rows.next()?;
+            if self.outline {
+                let columns = |y| self.corners.columns(y);
+                let (left, right) = outline_runs(columns(y), columns(y - 1), columns(y + 1));
+                self.current_scanline = self.scanline(y, left);
+                self.next_scanline = Some(self.scanline(y, right));
+            } else {
+                self.current_scanline = self.scanline(y, self.corners.columns(y));
+            }
+        }
+    }
+}
+
+/// Iterator over the horizontal runs of pixels of a filled rectangle with rounded corners.
+///
+/// Yields the row and the range of columns of each run, rows without pixels are skipped.
+#[derive(Clone, Debug)]
+pub struct RoundedRectangleSpans {
+    corners: Corners,
+    rows: Range<i32>,
+}
+
+impl RoundedRectangleSpans {
+    pub(crate) fn new(rect: &ivec2::Rectangle, radius: i32) -> Self {
+        Self {
+            corners: Corners::new(rect, radius),
+            rows: rect.rows(),
+        }
+    }
+}
+
+impl Iterator for RoundedRectangleSpans {
+    type Item = (i32, Range<i32>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let y = self.rows.next()?;
+            let columns = self.corners.columns(y);
+            if !columns.is_empty() {
+                return Some((y, columns));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn rect() -> ivec2::Rectangle {
+        ivec2::Rectangle::new(IVec2::new(2, 3), IVec2::new(20, 14))
+    }
+
+    fn inside(rect: &ivec2::Rectangle, point: IVec2) -> bool {
+        rect.columns().contains(&point.x) && rect.rows().contains(&point.y)
+    }
+
+    fn pixels(radius: i32, outline: bool) -> Vec<IVec2> {
+        RoundedRectanglePixels::new(&rect(), radius, outline, None).collect()
+    }
+
+    #[test]
+    fn test_corners_match_circle() {
+        for radius in 1..6 {
+            let points: HashSet<IVec2> = pixels(radius, false).into_iter().collect();
+            let corner = ivec2::Rectangle::new(rect().tl, rect().tl + IVec2::splat(radius));
+            let circle = ivec2::Circle::new(rect().tl + IVec2::splat(radius), radius);
+            let expected: HashSet<IVec2> = circle.pixel_iter(false).filter(|p| inside(&corner, *p)).collect();
+
+            let in_corner: HashSet<IVec2> = points.iter().copied().filter(|p| inside(&corner, *p)).collect();
+            assert_eq!(in_corner, expected, "radius {}", radius);
+        }
+    }
+
+    #[test]
+    fn test_no_radius_is_rectangle() {
+        let expected: Vec<IVec2> = rect().pixel_iter(false).collect();
+
+        assert_eq!(pixels(0, false), expected);
+        assert_eq!(pixels(-3, false), expected);
+    }
+
+    #[test]
+    fn test_outline() {
+        for radius in [0, 1, 3, 5, 100] {
+            let filled: HashSet<IVec2> = pixels(radius, false).into_iter().collect();
+            let outline = pixels(radius, true);
+            let expected: HashSet<IVec2> = filled
+                .iter()
+                .copied()
+                .filter(|p| [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y].iter().any(|d| !filled.contains(&(*p + *d))))
+                .collect();
+
+            assert_eq!(outline.len(), expected.len(), "radius {}", radius);
+            assert_eq!(outline.into_iter().collect::<HashSet<IVec2>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_contains_and_spans() {
+        let points = pixels(4, false);
+        let spans: Vec<IVec2> = RoundedRectangleSpans::new(&rect(), 4)
+            .flat_map(|(y, columns)| columns.map(move |x| IVec2::new(x, y)))
+            .collect();
+        assert_eq!(spans, points);
+
+        for y in 0..16 {
+            for x in 0..22 {
+                let point = IVec2::new(x, y);
+                assert_eq!(contains(&rect(), 4, point), points.contains(&point), "{:?}", point);
+            }
+        }
+    }
+
+    #[test]
+    fn test_clipped() {
+        let clip = ivec2::Rectangle::new(IVec2::new(0, 5), IVec2::new(6, 20));
+
+        for outline in [false, true] {
+            let expected: Vec<IVec2> = pixels(4, outline).into_iter().filter(|p| inside(&clip, *p)).collect();
+            let points: Vec<IVec2> = RoundedRectanglePixels::new(&rect(), 4, outline, Some(clip)).collect();
+
+            assert_eq!(points, expected);
+        }
+    }
+}
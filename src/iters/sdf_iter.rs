@@ -0,0 +1,132 @@
+use std::ops::Range;
+
+use glam::{IVec2, Vec2};
+
+use crate::SignedDistance;
+
+/// Returns the distance from `point` to the line segment from `start` to `end`.
+pub(crate) fn segment_distance(start: Vec2, end: Vec2, point: Vec2) -> f32 {
+    point.distance(crate::geometry::closest_point(start, end, point))
+}
+
+/// Returns the distance from `point` to the closed outline through `vertices`, negated if
+/// `inside` is `true`.
+pub(crate) fn outline_distance(vertices: &[Vec2], inside: bool, point: Vec2) -> f32 {
+    let distance = (0..vertices.len())
+        .map(|i| segment_distance(vertices[i], vertices[(i + 1) % vertices.len()], point))
+        .fold(f32::INFINITY, f32::min);
+
+    if inside {
+        -distance
+    } else {
+        distance
+    }
+}
+
+/// Iterator over the pixels of a shape given by its signed distance, yielding each pixel with
+/// its coverage in `0.0..=1.0`.
+///
+/// The distance is sampled at the pixel centers and the coverage falls off linearly over one
+/// pixel across the edge, which anti-aliases the edge. Pixels without coverage are skipped.
+#[derive(Clone, Debug)]
+pub struct SdfPixels<S> {
+    shape: S,
+    /// Width of the outline, `None` to fill the shape.
+    outline_width: Option<f32>,
+    rows: Range<i32>,
+    columns: Range<i32>,
+    x: Range<i32>,
+}
+
+impl<S: SignedDistance> SdfPixels<S> {
+    pub fn new(shape: S, outline_width: Option<f32>) -> Self {
+        let bounds = shape.distance_bounds();
+        // the edge can partially cover one more pixel, an outline reaches half its width further
+        let reach = 1 + outline_width.map_or(0, |width| (width.max(0.0) / 2.0).ceil() as i32);
+        let tl = bounds.tl - IVec2::splat(reach);
+        let br = bounds.br + IVec2::splat(reach);
+
+        Self {
+            shape,
+            outline_width,
+            rows: tl.y..br.y,
+            columns: tl.x..br.x,
+            x: 0..0,
+        }
+    }
+
+    fn coverage(&self, point: IVec2) -> f32 {
+        let distance = self.shape.signed_distance(point.as_vec2() + Vec2::splat(0.5));
+        let distance = match self.outline_width {
+            Some(width) => distance.abs() - width / 2.0,
+            None => distance,
+        };
+
+        (0.5 - distance).clamp(0.0, 1.0)
+    }
+}
+
+impl<S: SignedDistance> Iterator for SdfPixels<S> {
+    type Item = (IVec2, f32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(x) = self.x.next() else {
+                self.rows.next()?;
+                self.x = self.columns.clone();
+                continue;
+            };
+
+            let point = IVec2::new(x, self.rows.start - 1);
+            let coverage = self.coverage(point);
+            if coverage > 0.0 {
+                return Some((point, coverage));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::{ivec2, vec2};
+
+    #[test]
+    fn test_aligned_rectangle_matches_pixels() {
+        let rect = ivec2::Rectangle::new(IVec2::new(2, 1), IVec2::new(6, 4));
+        let pixels: Vec<(IVec2, f32)> = rect.aa_fill_iter().collect();
+        let expected: Vec<(IVec2, f32)> = rect.pixel_iter(false).map(|p| (p, 1.0)).collect();
+
+        assert_eq!(pixels, expected);
+    }
+
+    #[test]
+    fn test_circle_area() {
+        let circle = vec2::Circle::new(Vec2::new(10.3, 9.6), 6.5);
+        let area: f32 = circle.aa_fill_iter().map(|(_, coverage)| coverage).sum();
+
+        assert_abs_diff_eq!(area, std::f32::consts::PI * 6.5 * 6.5, epsilon = 0.5);
+    }
+
+    #[test]
+    fn test_outline() {
+        let circle = vec2::Circle::new(Vec2::new(10.0, 10.0), 5.0);
+        let pixels: Vec<(IVec2, f32)> = circle.aa_outline_iter(2.0).collect();
+
+        assert!(pixels.iter().all(|(p, _)| (p.as_vec2() + 0.5).distance(Vec2::splat(10.0)) < 7.0));
+        assert!(!pixels.iter().any(|(p, _)| *p == IVec2::new(10, 10)));
+        assert!(pixels.contains(&(IVec2::new(14, 10), 1.0)));
+    }
+
+    #[test]
+    fn test_line_segment_distance() {
+        let line = vec2::LineSegment::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0));
+
+        // end points are pixel centers
+        assert_abs_diff_eq!(line.signed_distance(Vec2::new(2.5, 0.5)), 0.0);
+        assert_abs_diff_eq!(line.signed_distance(Vec2::new(2.5, 3.5)), 3.0);
+        assert_abs_diff_eq!(line.signed_distance(Vec2::new(7.5, 4.5)), 5.0);
+    }
+}
@@ -0,0 +1,305 @@
+use std::ops::Range;
+
+use glam::{IVec2, Vec2};
+
+use super::common::Scanline;
+
+/// Shape used for the ends of a thick line.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum LineCap {
+    /// The line stops exactly at its end points.
+    #[default]
+    Butt,
+    /// The line is extended by half its width past the end points.
+    Square,
+    /// A half circle with the line width as diameter is added at both end points.
+    Round,
+}
+
+/// A line segment stroked with a width, in the coordinate space of the pixel centers.
+///
+/// Like the pixels of `LineIter`, the end points of the segment are pixel centers.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct ThickSegment {
+    start: Vec2,
+    end: Vec2,
+    direction: Vec2,
+    normal: Vec2,
+    length: f32,
+    half_width: f32,
+    cap: LineCap,
+}
+
+impl ThickSegment {
+    pub(crate) fn new(start: Vec2, end: Vec2, width: f32, cap: LineCap) -> Self {
+        let length = start.distance(end);
+        let direction = if length > 0.0 { (end - start) / length } else { Vec2::X };
+
+        Self {
+            start,
+            end,
+            direction,
+            normal: direction.perp(),
+            length,
+            half_width: width.max(0.0) / 2.0,
+            cap,
+        }
+    }
+
+    /// Returns `true` if the pixel center `point` is covered by the stroke.
+    ///
+    /// Across the line the covered band is half open, so a stroke of width `n` along an axis is
+    /// exactly `n` pixels wide.
+    pub(crate) fn contains(&self, point: Vec2) -> bool {
+        let offset = point - self.start;
+        let along = offset.dot(self.direction);
+        let across = offset.dot(self.normal);
+        let h = self.half_width;
+
+        if across < -h || across >= h {
+            return false;
+        }
+
+        match self.cap {
+            LineCap::Butt => (0.0..=self.length).contains(&along),
+            LineCap::Square => along >= -h && along < self.length + h,
+            LineCap::Round => {
+                (0.0..=self.length).contains(&along)
+                    || point.distance_squared(self.start) < h * h
+                    || point.distance_squared(self.end) < h * h
+            }
+        }
+    }
+
+    /// Returns the rows and columns of pixels that can be covered by the stroke.
+    pub(crate) fn bounds(&self) -> (Range<i32>, Range<i32>) {
+        let reach = Vec2::splat(self.half_width + 1.0);
+        let min = (self.start.min(self.end) - reach).floor().as_ivec2();
+        let max = (self.start.max(self.end) + reach).ceil().as_ivec2();
+
+        (min.y..max.y + 1, min.x..max.x + 1)
+    }
+
+    /// Returns the covered pixels of row `y`, the stroke is convex so they are contiguous.
+    pub(crate) fn row(&self, y: i32, columns: Range<i32>) -> Range<i32> {
+        convex_row(y, columns, self.row_seed(y as f32), |point| self.contains(point))
+    }
+
+    /// Returns a column of row `y` that is covered if any column of the row is, see `convex_row`.
+    pub(crate) fn row_seed(&self, y: f32) -> f32 {
+        let extend = match self.cap {
+            // the covered part of the row contains the point closest to the segment
+            LineCap::Round => return closest_on_row(self.start, self.end, y),
+            LineCap::Butt => 0.0,
+            LineCap::Square => self.half_width,
+        };
+
+        // the stroke is a rectangle, intersect the ranges of x inside its width and its length
+        let offset = y - self.start.y;
+        let along = linear_range(self.direction, offset, -extend, self.length + extend);
+        let across = linear_range(self.normal, offset, -self.half_width, self.half_width);
+        match (along, across) {
+            (Some(along), Some(across)) => self.start.x + (along.0.max(across.0) + along.1.min(across.1)) / 2.0,
+            _ => self.start.x,
+        }
+    }
+}
+
+/// Returns the range of `x` for which `axis.dot((x, offset))` lies between `min` and `max`, or
+/// `None` if there is no such `x`.
+fn linear_range(axis: Vec2, offset: f32, min: f32, max: f32) -> Option<(f32, f32)> {
+    let fixed = axis.y * offset;
+    if axis.x == 0.0 {
+        return (min..=max).contains(&fixed).then_some((f32::NEG_INFINITY, f32::INFINITY));
+    }
+    let (a, b) = ((min - fixed) / axis.x, (max - fixed) / axis.x);
+
+    Some((a.min(b), a.max(b)))
+}
+
+/// Returns the x coordinate of the point of row `y` closest to the segment from `start` to
+/// `end`, the middle of the segment if it runs along the row.
+pub(crate) fn closest_on_row(start: Vec2, end: Vec2, y: f32) -> f32 {
+    let t = if start.y == end.y {
+        0.5
+    } else {
+        ((y - start.y) / (end.y - start.y)).clamp(0.0, 1.0)
+    };
+
+    start.x + (end.x - start.x) * t
+}
+
+/// Returns the pixels of row `y` inside a convex region, searching only the given `columns`.
+///
+/// `seed` is a column from the geometry of the region: if any pixel of the row is inside, one of
+/// the two columns around `seed` is. The edges of the row are then found with a binary search on
+/// either side of it.
+pub(crate) fn convex_row(y: i32, columns: Range<i32>, seed: f32, contains: impl Fn(Vec2) -> bool) -> Range<i32> {
+    if columns.is_empty() {
+        return 0..0;
+    }
+    let inside = |x: i32| contains(IVec2::new(x, y).as_vec2());
+    let clamp = |x: f32| (x as i32).clamp(columns.start, columns.end - 1);
+
+    let Some(start) = [clamp(seed.floor()), clamp(seed.ceil())].into_iter().find(|x| inside(*x)) else {
+        return 0..0;
+    };
+    let first = partition_point(columns.start..start, |x| !inside(x));
+    let end = partition_point(start + 1..columns.end, inside);
+
+    first..end
+}
+
+/// Returns the first value of `range` for which `predicate` is `false`, the values for which it is
+/// `true` must come first.
+fn partition_point(range: Range<i32>, predicate: impl Fn(i32) -> bool) -> i32 {
+    let (mut low, mut high) = (range.start, range.end);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if predicate(middle) {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+
+    low
+}
+
+/// Iterator over the pixels of a line stroked with a width.
+///
+/// Each covered pixel is yielded exactly once, in scanline order.
+#[derive(Clone, Debug)]
+pub struct ThickLineIter {
+    segment: ThickSegment,
+    rows: Range<i32>,
+    columns: Range<i32>,
+    current_scanline: Scanline,
+}
+
+impl ThickLineIter {
+    pub fn new(start: Vec2, end: Vec2, width: f32, cap: LineCap) -> Self {
+        let segment = ThickSegment::new(start, end, width, cap);
+        let (rows, columns) = segment.bounds();
+
+        Self {
+            segment,
+            rows,
+            columns,
+            current_scanline: Scanline::new_empty(0),
+        }
+    }
+}
+
+impl Iterator for ThickLineIter {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.current_scanline.next() {
+                return Some(point);
+            }
+            let y = self.rows.next()?;
+            self.current_scanline = Scanline::new(y, self.segment.row(y, self.columns.clone()), false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn pixels(start: Vec2, end: Vec2, width: f32, cap: LineCap) -> Vec<IVec2> {
+        ThickLineIter::new(start, end, width, cap).collect()
+    }
+
+    #[test]
+    fn test_butt() {
+        let points = pixels(Vec2::new(1.0, 1.0), Vec2::new(4.0, 1.0), 2.0, LineCap::Butt);
+
+        assert_eq!(
+            points,
+            vec![
+                IVec2::new(1, 0),
+                IVec2::new(2, 0),
+                IVec2::new(3, 0),
+                IVec2::new(4, 0),
+                IVec2::new(1, 1),
+                IVec2::new(2, 1),
+                IVec2::new(3, 1),
+                IVec2::new(4, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_width_one_matches_line() {
+        let points = pixels(Vec2::new(2.0, 3.0), Vec2::new(2.0, 7.0), 1.0, LineCap::Butt);
+        let expected: Vec<IVec2> = (3..=7).map(|y| IVec2::new(2, y)).collect();
+
+        assert_eq!(points, expected);
+    }
+
+    #[test]
+    fn test_square() {
+        let points = pixels(Vec2::new(1.0, 1.0), Vec2::new(4.0, 1.0), 3.0, LineCap::Square);
+
+        assert_eq!(points.len(), 3 * 6);
+        assert!(points.contains(&IVec2::new(0, 0)));
+        assert!(points.contains(&IVec2::new(5, 2)));
+        assert!(!points.contains(&IVec2::new(6, 1)));
+    }
+
+    #[test]
+    fn test_round() {
+        let butt = pixels(Vec2::new(5.0, 5.0), Vec2::new(12.0, 9.0), 5.0, LineCap::Butt);
+        let round = pixels(Vec2::new(5.0, 5.0), Vec2::new(12.0, 9.0), 5.0, LineCap::Round);
+
+        assert!(butt.iter().all(|p| round.contains(p)));
+        assert!(round.len() > butt.len());
+        assert!(round.contains(&IVec2::new(3, 5)));
+        assert!(!butt.contains(&IVec2::new(3, 5)));
+    }
+
+    #[test]
+    fn test_no_duplicates() {
+        for cap in [LineCap::Butt, LineCap::Square, LineCap::Round] {
+            let points = pixels(Vec2::new(-3.0, 2.0), Vec2::new(9.5, -4.25), 4.5, cap);
+            let unique: HashSet<IVec2> = points.iter().copied().collect();
+
+            assert_eq!(points.len(), unique.len());
+        }
+    }
+
+    #[test]
+    fn test_rows_match_scan() {
+        let ends = [
+            (Vec2::new(1.0, 1.0), Vec2::new(4.0, 1.0)),
+            (Vec2::new(2.0, 3.0), Vec2::new(2.0, 7.0)),
+            (Vec2::new(-3.0, 2.0), Vec2::new(9.5, -4.25)),
+            (Vec2::new(0.3, -5.7), Vec2::new(2.1, 14.2)),
+            (Vec2::new(5.0, 5.0), Vec2::new(5.0, 5.0)),
+            (Vec2::new(0.0, 0.0), Vec2::new(30.0, 1.0)),
+        ];
+
+        for (start, end) in ends {
+            for width in [0.5, 1.0, 2.0, 3.5, 6.0] {
+                for cap in [LineCap::Butt, LineCap::Square, LineCap::Round] {
+                    let segment = ThickSegment::new(start, end, width, cap);
+                    let (rows, columns) = segment.bounds();
+                    for y in rows {
+                        let covered: Vec<i32> = columns
+                            .clone()
+                            .filter(|x| segment.contains(IVec2::new(*x, y).as_vec2()))
+                            .collect();
+                        let row: Vec<i32> = segment.row(y, columns.clone()).collect();
+
+                        assert_eq!(row, covered, "{:?} {:?} {} {:?} row {}", start, end, width, cap, y);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use std::ops::Range;
+
+use glam::{I64Vec2, IVec2, Vec2};
+
+use super::common::Scanline;
+use crate::ivec2;
+
+/// Number of fractional bits used for the fixed point vertex positions.
+const SUBPIXEL_BITS: u32 = 8;
+const SUBPIXEL_SCALE: i64 = 1 << SUBPIXEL_BITS;
+const HALF_PIXEL: i64 = SUBPIXEL_SCALE / 2;
+
+/// Returns `true` if the pixel at `coord` is filled by the triangle.
+///
+/// Uses the same edge functions and top-left rule as `TrianglePixels`.
+pub(crate) fn contains(vertices: [Vec2; 3], coord: IVec2) -> bool {
+    let Some(edges) = edges(vertices) else {
+        return false;
+    };
+    let center = coord.as_i64vec2() * SUBPIXEL_SCALE + I64Vec2::splat(HALF_PIXEL);
+
+    edges.iter().all(|edge| edge.value(center) >= edge.bias)
+}
+
+/// Iterator over the pixels of a triangle.
+///
+/// Pixels are filled following the top-left rule: a pixel whose center lies exactly on an edge is
+/// only filled if that edge is a top or a left edge. Two triangles sharing an edge therefore never
+/// both fill the pixels along it, and never leave a gap between them.
+#[derive(Clone, Debug)]
+pub struct TrianglePixels {
+    scanlines: Scanlines,
+    current_scanline: Scanline,
+}
+
+impl TrianglePixels {
+    pub(crate) fn new(vertices: [Vec2; 3], limits: Option<ivec2::Rectangle>, outline: bool) -> Self {
+        Self {
+            scanlines: Scanlines::new(vertices, limits, outline),
+            current_scanline: Scanline::new_empty(0),
+        }
+    }
+}
+
+impl Iterator for TrianglePixels {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.current_scanline.next() {
+                return Some(point);
+            }
+            self.current_scanline = self.scanlines.next()?;
+        }
+    }
+}
+
+/// Iterator over the horizontal runs of pixels of a filled triangle.
+///
+/// Yields the row and the range of columns of each run following the top-left rule, rows without pixels are skipped.
+#[derive(Clone, Debug)]
+pub struct TriangleSpans {
+    scanlines: Scanlines,
+}
+
+impl TriangleSpans {
+    pub(crate) fn new(vertices: [Vec2; 3], limits: Option<ivec2::Rectangle>) -> Self {
+        Self {
+            scanlines: Scanlines::new(vertices, limits, false),
+        }
+    }
+}
+
+impl Iterator for TriangleSpans {
+    type Item = (i32, Range<i32>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let scanline = self.scanlines.next()?;
+            if !scanline.is_empty() {
+                return Some((scanline.y, scanline.x));
+            }
+        }
+    }
+}
+
+/// Edge function of a triangle edge in fixed point coordinates.
+///
+/// The value is positive on the inside of the triangle.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Edge {
+    start: I64Vec2,
+    delta: I64Vec2,
+    /// Minimum value of the edge function for a pixel center to count as inside.
+    bias: i64,
+}
+
+impl Edge {
+    fn new(start: I64Vec2, end: I64Vec2) -> Self {
+        let delta = end - start;
+        // with y pointing down, the inside lies below top edges and right of left edges
+        let is_top = delta.y == 0 && delta.x > 0;
+        let is_left = delta.y < 0;
+
+        Self {
+            start,
+            delta,
+            bias: if is_top || is_left { 0 } else { 1 },
+        }
+    }
+
+    fn value(&self, point: I64Vec2) -> i64 {
+        self.delta.x * (point.y - self.start.y) - self.delta.y * (point.x - self.start.x)
+    }
+
+    /// Returns the range of pixel columns in row `y` that are inside this edge.
+    fn columns(&self, y: i32) -> Range<i64> {
+        let center_y = y as i64 * SUBPIXEL_SCALE + HALF_PIXEL;
+        // value of the edge function at the center of pixel `x` is `offset + slope * x`
+        let slope = -self.delta.y * SUBPIXEL_SCALE;
+        let offset = self.delta.x * (center_y - self.start.y) - self.delta.y * (HALF_PIXEL - self.start.x);
+
+        match slope.signum() {
+            1 => ceil_div(self.bias - offset, slope)..i64::MAX,
+            -1 => i64::MIN..(offset - self.bias).div_euclid(-slope) + 1,
+            _ if offset >= self.bias => i64::MIN..i64::MAX,
+            _ => 0..0,
+        }
+    }
+}
+
+fn ceil_div(a: i64, b: i64) -> i64 {
+    -(-a).div_euclid(b)
+}
+
+fn to_fixed(v: Vec2) -> I64Vec2 {
+    (v * SUBPIXEL_SCALE as f32).round().as_i64vec2()
+}
+
+/// Returns the edges of the triangle wound so that the inside is positive, or `None` if the
+/// triangle has no area.
+fn edges(vertices: [Vec2; 3]) -> Option<[Edge; 3]> {
+    let [a, mut b, mut c] = vertices.map(to_fixed);
+
+    let area = (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x);
+    if area == 0 {
+        return None;
+    }
+    if area < 0 {
+        std::mem::swap(&mut b, &mut c);
+    }
+
+    Some([Edge::new(a, b), Edge::new(b, c), Edge::new(c, a)])
+}
+
+#[derive(Clone, Debug)]
+struct Scanlines {
+    outline: bool,
+    edges: [Edge; 3],
+    rows: Range<i32>,
+    columns: Range<i64>,
+    limits: Option<ivec2::Rectangle>,
+}
+
+impl Scanlines {
+    fn new(vertices: [Vec2; 3], limits: Option<ivec2::Rectangle>, outline: bool) -> Self {
+        let Some(edges) = edges(vertices) else {
+            return Self {
+                outline,
+                edges: [Edge::new(I64Vec2::ZERO, I64Vec2::ZERO); 3],
+                rows: 0..0,
+                columns: 0..0,
+                limits,
+            };
+        };
+
+        let min = edges[0].start.min(edges[1].start).min(edges[2].start);
+        let max = edges[0].start.max(edges[1].start).max(edges[2].start);
+        // pixels whose centers lie inside the bounding box of the vertices
+        let first = I64Vec2::new(
+            ceil_div(min.x - HALF_PIXEL, SUBPIXEL_SCALE),
+            ceil_div(min.y - HALF_PIXEL, SUBPIXEL_SCALE),
+        );
+        let last = (max - I64Vec2::splat(HALF_PIXEL)).div_euclid(I64Vec2::splat(SUBPIXEL_SCALE));
+
+        let rows = first.y as i32..last.y as i32 + 1;
+
+        Self {
+            outline,
+            edges,
+            rows: match limits {
+                Some(limits) => rows.start.max(limits.tl.y)..rows.end.min(limits.br.y),
+                None => rows,
+            },
+            columns: first.x..last.x + 1,
+            limits,
+        }
+    }
+}
+
+impl Iterator for Scanlines {
+    type Item = Scanline;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let y = self.rows.next()?;
+
+        let columns = self.edges.iter().fold(self.columns.clone(), |columns, edge| {
+            let inside = edge.columns(y);
+            columns.start.max(inside.start)..columns.end.min(inside.end)
+        });
+
+        if columns.is_empty() {
+            return Some(Scanline::new_empty(y));
+        }
+
+        let columns = columns.start as i32..columns.end as i32;
+        Some(match self.limits {
+            Some(limits) => Scanline::new_clipped(y, columns, self.outline, &limits.columns()),
+            None => Scanline::new(y, columns, self.outline),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::ivec2;
+
+    fn pixels(vertices: [Vec2; 3]) -> Vec<IVec2> {
+        TrianglePixels::new(vertices, None, false).collect()
+    }
+
+    #[test]
+    fn test_iter() {
+        let points = pixels([Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(0.0, 4.0)]);
+
+        assert_eq!(
+            points,
+            vec![
+                IVec2::new(0, 0),
+                IVec2::new(1, 0),
+                IVec2::new(2, 0),
+                IVec2::new(0, 1),
+                IVec2::new(1, 1),
+                IVec2::new(0, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_winding_does_not_matter() {
+        let clockwise = [Vec2::new(1.0, 1.0), Vec2::new(9.0, 3.0), Vec2::new(4.0, 8.0)];
+        let counter_clockwise = [clockwise[0], clockwise[2], clockwise[1]];
+
+        assert_eq!(pixels(clockwise), pixels(counter_clockwise));
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let points = pixels([Vec2::new(0.0, 0.0), Vec2::new(4.0, 4.0), Vec2::new(8.0, 8.0)]);
+        assert!(points.is_empty());
+    }
+
+    /// A fan of triangles around the center of a square covers every pixel of the square once.
+    #[test]
+    fn test_shared_edges() {
+        let corners = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(8.0, 0.0),
+            Vec2::new(8.0, 8.0),
+            Vec2::new(0.0, 8.0),
+        ];
+        for center in [Vec2::new(4.0, 4.0), Vec2::new(3.0, 5.5), Vec2::new(2.25, 6.75)] {
+            let mut seen = HashSet::new();
+            for i in 0..4 {
+                for point in pixels([center, corners[i], corners[(i + 1) % 4]]) {
+                    assert!(seen.insert(point), "{:?} filled twice", point);
+                }
+            }
+
+            let rect = ivec2::Rectangle::new(IVec2::new(0, 0), IVec2::new(8, 8));
+            assert_eq!(seen, rect.pixel_iter(false).collect::<HashSet<IVec2>>());
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        let vertices = [Vec2::new(-3.0, -1.0), Vec2::new(9.0, 2.0), Vec2::new(1.0, 10.0)];
+        let limits = ivec2::Rectangle::new(IVec2::new(0, 0), IVec2::new(5, 5));
+
+        for outline in [false, true] {
+            let expected: Vec<IVec2> = TrianglePixels::new(vertices, None, outline)
+                .filter(|p| limits.columns().contains(&p.x) && limits.rows().contains(&p.y))
+                .collect();
+            let points: Vec<IVec2> = TrianglePixels::new(vertices, Some(limits), outline).collect();
+
+            assert_eq!(points, expected);
+        }
+    }
+
+    #[test]
+    fn test_contains_matches_pixels() {
+        let vertices = [Vec2::new(0.0, 0.0), Vec2::new(6.0, 6.0), Vec2::new(0.0, 6.0)];
+        let points = pixels(vertices);
+
+        for y in -1..8 {
+            for x in -1..8 {
+                let coord = IVec2::new(x, y);
+                assert_eq!(contains(vertices, coord), points.contains(&coord), "{:?}", coord);
+            }
+        }
+    }
+}
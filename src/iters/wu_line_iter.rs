@@ -0,0 +1,237 @@
+use glam::{IVec2, Vec2};
+
+/// Anti-aliased line iterator using Xiaolin Wu's algorithm.
+///
+/// Yields every touched pixel together with its coverage in `0.0..=1.0`, walking from the start
+/// of the line to its end. Line end points are pixel centers, the same as for `LineIter`. Each
+/// step along the major axis yields up to two pixels, pixels with no coverage are skipped.
+#[derive(Debug, Copy, Clone)]
+pub struct WuLineIter {
+    steep: bool,
+    /// Start point with the major axis in `x`.
+    start: Vec2,
+    gradient: f32,
+    step: i32,
+    first: i32,
+    current: i32,
+    last: i32,
+    start_gap: f32,
+    end_gap: f32,
+    done: bool,
+    pending: Option<(IVec2, f32)>,
+}
+
+impl WuLineIter {
+    pub fn new(start: Vec2, end: Vec2) -> Self {
+        let steep = (end.y - start.y).abs() > (end.x - start.x).abs();
+        let (start, end) = if steep {
+            (Vec2::new(start.y, start.x), Vec2::new(end.y, end.x))
+        } else {
+            (start, end)
+        };
+
+        let delta = end - start;
+        let gradient = if delta.x == 0.0 { 0.0 } else { delta.y / delta.x };
+        let step = if delta.x < 0.0 { -1 } else { 1 };
+
+        // columns are `x - 0.5..x + 0.5` around the pixel centers, also left of zero
+        let first = (start.x + 0.5).floor() as i32;
+        let last = (end.x + 0.5).floor() as i32;
+        let (start_offset, end_offset) = ((start.x + 0.5).rem_euclid(1.0), (end.x + 0.5).rem_euclid(1.0));
+
+        // portion of the first and last column that is covered along the major axis
+        let (start_gap, end_gap) = if delta.x == 0.0 {
+            // a line of zero length still draws its pixel, like `LineIter`
+            (1.0, 1.0)
+        } else if first == last {
+            (delta.x.abs(), delta.x.abs())
+        } else if step > 0 {
+            (1.0 - start_offset, end_offset)
+        } else {
+            (start_offset, 1.0 - end_offset)
+        };
+
+        Self {
+            steep,
+            start,
+            gradient,
+            step,
+            first,
+            current: first,
+            last,
+            start_gap,
+            end_gap,
+            done: false,
+            pending: None,
+        }
+    }
+
+    fn pixel(&self, major: i32, minor: i32) -> IVec2 {
+        if self.steep {
+            IVec2::new(minor, major)
+        } else {
+            IVec2::new(major, minor)
+        }
+    }
+}
+
+impl Iterator for WuLineIter {
+    type Item = (IVec2, f32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(pending) = self.pending.take() {
+                return Some(pending);
+            }
+            if self.done {
+                return None;
+            }
+
+            let x = self.current;
+            let gap = if x == self.first {
+                self.start_gap
+            } else if x == self.last {
+                self.end_gap
+            } else {
+                1.0
+            };
+
+            if x == self.last {
+                self.done = true;
+            } else {
+                self.current += self.step;
+            }
+
+            let y = self.start.y + self.gradient * (x as f32 - self.start.x);
+            let minor = y.floor();
+            let fraction = y - minor;
+
+            let far = (self.pixel(x, minor as i32 + 1), fraction * gap);
+            if far.1 > 0.0 {
+                self.pending = Some(far);
+            }
+
+            let near = (self.pixel(x, minor as i32), (1.0 - fraction) * gap);
+            if near.1 > 0.0 {
+                return Some(near);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_horizontal() {
+        let pixels: Vec<(IVec2, f32)> = WuLineIter::new(Vec2::new(0.0, 2.0), Vec2::new(3.0, 2.0)).collect();
+
+        assert_eq!(
+            pixels,
+            vec![
+                (IVec2::new(0, 2), 0.5),
+                (IVec2::new(1, 2), 1.0),
+                (IVec2::new(2, 2), 1.0),
+                (IVec2::new(3, 2), 0.5),
+            ]
+        );
+    }
+
+    /// Columns left of zero are covered the same as columns right of it.
+    #[test]
+    fn test_negative_coordinates() {
+        let pixels: Vec<(IVec2, f32)> = WuLineIter::new(Vec2::new(-4.0, 0.0), Vec2::new(-1.0, 0.0)).collect();
+        assert_eq!(
+            pixels,
+            vec![
+                (IVec2::new(-4, 0), 0.5),
+                (IVec2::new(-3, 0), 1.0),
+                (IVec2::new(-2, 0), 1.0),
+                (IVec2::new(-1, 0), 0.5),
+            ]
+        );
+
+        let backward: Vec<(IVec2, f32)> = WuLineIter::new(Vec2::new(-1.0, -3.0), Vec2::new(-1.0, -6.0)).collect();
+        assert_eq!(
+            backward,
+            vec![
+                (IVec2::new(-1, -3), 0.5),
+                (IVec2::new(-1, -4), 1.0),
+                (IVec2::new(-1, -5), 1.0),
+                (IVec2::new(-1, -6), 0.5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_crossing_zero() {
+        let pixels: Vec<(IVec2, f32)> = WuLineIter::new(Vec2::new(-1.0, 0.0), Vec2::new(3.0, 0.0)).collect();
+        assert_eq!(pixels.first(), Some(&(IVec2::new(-1, 0), 0.5)));
+        assert_eq!(pixels.last(), Some(&(IVec2::new(3, 0), 0.5)));
+        assert_eq!(pixels.len(), 5);
+
+        let start = Vec2::new(-7.0, -3.0);
+        let end = Vec2::new(5.0, 2.0);
+        for (from, to) in [(start, end), (end, start)] {
+            let pixels: Vec<(IVec2, f32)> = WuLineIter::new(from, to).collect();
+            assert!(pixels.iter().all(|(_, coverage)| (0.0..=1.0).contains(coverage)));
+            for x in -6..5 {
+                let coverage: f32 = pixels.iter().filter(|(p, _)| p.x == x).map(|(_, c)| c).sum();
+                assert_abs_diff_eq!(coverage, 1.0, epsilon = 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_zero_length() {
+        let pixels: Vec<(IVec2, f32)> = WuLineIter::new(Vec2::new(-2.0, 3.0), Vec2::new(-2.0, 3.0)).collect();
+        assert_eq!(pixels, vec![(IVec2::new(-2, 3), 1.0)]);
+    }
+
+    #[test]
+    fn test_between_rows() {
+        let pixels: Vec<(IVec2, f32)> = WuLineIter::new(Vec2::new(0.5, 1.25), Vec2::new(3.5, 1.25)).collect();
+
+        assert_eq!(
+            pixels,
+            vec![
+                (IVec2::new(1, 1), 0.75),
+                (IVec2::new(1, 2), 0.25),
+                (IVec2::new(2, 1), 0.75),
+                (IVec2::new(2, 2), 0.25),
+                (IVec2::new(3, 1), 0.75),
+                (IVec2::new(3, 2), 0.25),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coverage_per_column() {
+        let start = Vec2::new(1.0, 1.0);
+        let end = Vec2::new(11.0, 5.0);
+        let pixels: Vec<(IVec2, f32)> = WuLineIter::new(start, end).collect();
+
+        for x in 2..11 {
+            let coverage: f32 = pixels.iter().filter(|(p, _)| p.x == x).map(|(_, c)| c).sum();
+            assert_abs_diff_eq!(coverage, 1.0, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_direction() {
+        let forward: Vec<(IVec2, f32)> = WuLineIter::new(Vec2::new(2.0, 1.0), Vec2::new(4.0, 9.0)).collect();
+        let backward: Vec<(IVec2, f32)> = WuLineIter::new(Vec2::new(4.0, 9.0), Vec2::new(2.0, 1.0)).collect();
+
+        assert_eq!(forward.first().unwrap().0, IVec2::new(2, 1));
+        assert_eq!(backward.first().unwrap().0, IVec2::new(4, 9));
+
+        let mut reversed: Vec<IVec2> = backward.iter().map(|(p, _)| *p).collect();
+        let mut forward: Vec<IVec2> = forward.iter().map(|(p, _)| *p).collect();
+        reversed.sort_by_key(|p| (p.y, p.x));
+        forward.sort_by_key(|p| (p.y, p.x));
+        assert_eq!(forward, reversed);
+    }
+}
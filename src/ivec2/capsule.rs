@@ -0,0 +1,167 @@
+use glam::IVec2;
+use glam::Vec2;
+use crate::iters::capsule_iter::{CapsuleGeometry, CapsulePixels};
+use super::line_segment::LineSegment;
+use super::rectangle::Rectangle;
+
+/// A capsule, the area within `radius` of a line segment.
+///
+/// The end points of the segment are positions like the position of a `Circle`, and the pixels
+/// are tested with the same thresholds. A capsule with a zero length segment has the same pixels
+/// as a circle with the same position and radius.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Capsule {
+    pub segment: LineSegment,
+    pub radius: i32,
+}
+
+impl Capsule {
+    pub const fn new(segment: LineSegment, radius: i32) -> Self {
+        Self { segment, radius }
+    }
+
+    /// Returns the bounds of the capsule in its own coordinates.
+    pub fn bounds(&self) -> Rectangle {
+        let r = IVec2::splat(self.radius);
+        Rectangle::new(
+            self.segment.start.min(self.segment.end) - r,
+            self.segment.start.max(self.segment.end) + r,
+        )
+    }
+
+    /// Returns an iterator over the pixels of the capsule.
+    ///
+    /// The outline consists of the filled pixels next to a pixel that is not filled.
+    pub fn pixel_iter(&self, outline: bool) -> CapsulePixels {
+        CapsulePixels::new(self.geometry(), outline, None)
+    }
+
+    /// Returns an iterator over the pixels of the shape that lie inside `clip`, the same pixels
+    /// `pixel_iter` yields inside of it.
+    pub fn pixel_iter_clipped(&self, outline: bool, clip: crate::ivec2::Rectangle) -> CapsulePixels {
+        CapsulePixels::new(self.geometry(), outline, Some(clip))
+    }
+
+    /// Returns the distance from `point` to the capsule, `0.0` if the point is inside of it.
+    ///
+    /// Points are in pixel space like for `SignedDistance`.
+    pub fn distance(&self, point: Vec2) -> f32 {
+        crate::SignedDistance::signed_distance(self, point).max(0.0)
+    }
+
+    fn geometry(&self) -> CapsuleGeometry {
+        let threshold = super::circle::diameter_to_threshold(self.radius * 2);
+        CapsuleGeometry::new(
+            self.segment.start.as_vec2(),
+            self.segment.end.as_vec2(),
+            self.radius as f32,
+            threshold as f32,
+        )
+        }
+    
+    
+    pub fn as_capsule_vec2(&self) -> crate::vec2::Capsule {
+        crate::vec2::Capsule::new(self.segment.as_vec2(), self.radius as f32)
+    }
+    
+    
+    pub fn as_capsule_uvec2(&self) -> crate::uvec2::Capsule {
+        crate::uvec2::Capsule::new(self.segment.as_uvec2(), self.radius as u32)
+    }
+    
+}
+
+impl crate::Shape for Capsule {
+    type Vector = IVec2;
+    type Scalar = i32;
+
+    fn position(&self) -> IVec2 {
+        self.bounds().tl
+        }
+
+    /// Returns the midpoint of the segment.
+    fn center(&self) -> IVec2 {
+        (self.segment.start + self.segment.end) / 2
+    }
+
+    /// Returns `true` if the pixel at `coord` is filled.
+    fn contains(&self, coord: IVec2) -> bool {
+        self.geometry().contains(coord)
+    }
+
+    fn bounding_box(&self) -> crate::ivec2::Rectangle {
+        self.geometry().bounding_box()
+    }
+
+    fn fill_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(false)
+    }
+
+    fn outline_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(true)
+    }
+
+    fn fill_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(false, clip)
+    }
+
+    fn outline_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(true, clip)
+    }
+}
+
+impl crate::SignedDistance for Capsule {
+    fn signed_distance(&self, point: Vec2) -> f32 {
+        let (start, end, radius) = (self.segment.start.as_vec2(), self.segment.end.as_vec2(), self.radius as f32);
+        crate::iters::sdf_iter::segment_distance(start, end, point) - radius
+    }
+
+    fn distance_bounds(&self) -> crate::ivec2::Rectangle {
+        self.geometry().bounding_box()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Shape;
+
+    #[test]
+    fn test_contains() {
+        let capsule = Capsule::new(
+            LineSegment::new(IVec2::new(4, 4), IVec2::new(12, 4)),
+            2,
+        );
+
+        assert!(capsule.contains(IVec2::new(8, 2)));
+        assert!(capsule.contains(IVec2::new(2, 3)));
+        assert!(!capsule.contains(IVec2::new(8, 6)));
+        assert!(!capsule.contains(IVec2::new(2, 2)));
+        assert_eq!(
+            capsule.bounds(),
+            Rectangle::new(IVec2::new(2, 2), IVec2::new(14, 6))
+        );
+        assert!(capsule.pixel_iter(false).all(|p| crate::ivec2::Rectangle::new(IVec2::new(2, 2), IVec2::new(14, 6)).contains(p)));
+
+        assert_eq!(capsule.distance(Vec2::new(8.0, 4.0)), 0.0);
+        assert_eq!(capsule.distance(Vec2::new(8.0, 10.0)), 4.0);
+        assert_eq!(capsule.distance(Vec2::new(17.0, 4.0)), 3.0);
+    }
+
+    /// A capsule within `radius` of the origin reaches past zero.
+    #[test]
+    fn test_near_origin() {
+        let capsule = Capsule::new(
+            LineSegment::new(IVec2::new(1, 1), IVec2::new(5, 1)),
+            2,
+        );
+        let bounding_box = crate::ivec2::Rectangle::new(IVec2::new(-1, -1), IVec2::new(7, 3));
+
+        assert_eq!(Shape::bounding_box(&capsule), bounding_box);
+        assert_eq!(crate::DynShape::bounding_box(&capsule), bounding_box);
+        assert_eq!(crate::SignedDistance::distance_bounds(&capsule), bounding_box);
+        assert_eq!(capsule.fill_iter().count(), 28);
+        assert!(capsule.fill_iter().all(|p| bounding_box.columns().contains(&p.x) && bounding_box.rows().contains(&p.y)));
+    }
+}
@@ -0,0 +1,136 @@
+use glam::IVec2;
+use glam::Vec2;
+use crate::iters::bezier_iter::BezierPixels;
+
+/// A cubic Bézier curve, leaving the start towards `control1` and arriving at the end from
+/// `control2`.
+///
+/// Like the end points of a `LineSegment`, the points are pixel centers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct CubicBezier {
+    pub start: IVec2,
+    pub control1: IVec2,
+    pub control2: IVec2,
+    pub end: IVec2,
+}
+
+impl CubicBezier {
+    pub const fn new(
+        start: IVec2,
+        control1: IVec2,
+        control2: IVec2,
+        end: IVec2,
+    ) -> Self {
+        Self { start, control1, control2, end }
+    }
+
+    pub fn points(&self) -> [IVec2; 4] {
+        [self.start, self.control1, self.control2, self.end]
+    }
+
+    /// Returns the point on the curve at `t` in `0.0..=1.0`.
+    pub fn point_at(&self, t: f32) -> Vec2 {
+        crate::iters::bezier_iter::point_at(&self.points_vec2(), t)
+    }
+
+    /// Returns line segments that approximate the curve to within `tolerance` pixels.
+    ///
+    /// The curve is subdivided adaptively, so flat parts use fewer segments than tight bends. The
+    /// segments are connected and run from the start to the end of the curve.
+    pub fn flatten(&self, tolerance: f32) -> Vec<crate::vec2::LineSegment> {
+        crate::iters::bezier_iter::flatten(&self.points_vec2(), tolerance)
+            .windows(2)
+            .map(|pair| crate::vec2::LineSegment::new(pair[0], pair[1]))
+            .collect()
+    }
+
+    /// Returns an iterator over the pixels of the curve, a connected 1px path from the start to
+    /// the end in which no pixel appears twice.
+    pub fn pixel_iter(&self) -> BezierPixels {
+        BezierPixels::new(&self.points_vec2())
+    }
+
+    fn points_vec2(&self) -> [Vec2; 4] {
+        self.points().map(|p| p.as_vec2())
+        }
+
+    /// Returns the points moved to the centers of their pixels.
+    fn pixel_points(&self) -> [Vec2; 4] {
+        self.points_vec2().map(|p| p + Vec2::splat(0.5))
+    }
+    
+    
+    pub fn as_cubic_bezier_vec2(&self) -> crate::vec2::CubicBezier {
+        crate::vec2::CubicBezier::new(
+            self.start.as_vec2(),
+            self.control1.as_vec2(),
+            self.control2.as_vec2(),
+            self.end.as_vec2(),
+        )
+    }
+    
+    
+    pub fn as_cubic_bezier_uvec2(&self) -> crate::uvec2::CubicBezier {
+        crate::uvec2::CubicBezier::new(
+            self.start.as_uvec2(),
+            self.control1.as_uvec2(),
+            self.control2.as_uvec2(),
+            self.end.as_uvec2(),
+        )
+    }
+    
+}
+
+impl crate::SignedDistance for CubicBezier {
+    /// Returns the distance to the curve, which has no inside. The points are pixel centers, use
+    /// `aa_outline_iter` to draw the curve with a width.
+    fn signed_distance(&self, point: Vec2) -> f32 {
+        crate::iters::bezier_iter::bezier_distance(&self.pixel_points(), point)
+    }
+
+    fn distance_bounds(&self) -> crate::ivec2::Rectangle {
+        crate::iters::bezier_iter::bezier_bounds(&self.pixel_points())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_pixel_iter() {
+        let curve = CubicBezier::new(
+            IVec2::new(0, 0),
+            IVec2::new(0, 16),
+            IVec2::new(20, 16),
+            IVec2::new(20, 0),
+        );
+        assert_eq!(curve.point_at(0.5), Vec2::new(10.0, 12.0));
+        assert!(curve.pixel_iter().any(|p| p == glam::IVec2::new(10, 12)));
+
+        let flattened = curve.flatten(0.5);
+        assert_eq!(flattened.first().unwrap().start, Vec2::new(0.0, 0.0));
+        assert_eq!(flattened.last().unwrap().end, Vec2::new(20.0, 0.0));
+        assert!(flattened.windows(2).all(|pair| pair[0].end == pair[1].start));
+    }
+
+    #[test]
+    fn test_signed_distance() {
+        use crate::SignedDistance;
+
+        let curve = CubicBezier::new(
+            IVec2::new(0, 0),
+            IVec2::new(0, 16),
+            IVec2::new(20, 16),
+            IVec2::new(20, 0),
+        );
+
+        assert_eq!(curve.signed_distance(Vec2::new(10.5, 12.5)), 0.0);
+        assert_abs_diff_eq!(curve.signed_distance(Vec2::new(10.5, 15.5)), 3.0, epsilon = 1e-4);
+        assert_eq!(curve.distance_bounds(), crate::ivec2::Rectangle::new(glam::IVec2::new(0, 0), glam::IVec2::new(21, 17)));
+        assert!(curve.aa_outline_iter(1.0).any(|(p, _)| p == glam::IVec2::new(10, 12)));
+    }
+}
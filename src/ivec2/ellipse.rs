@@ -0,0 +1,198 @@
+use glam::IVec2;
+use crate::iters::ellipse_iter::{EllipsePoints, EllipseSpans};
+use super::rectangle::Rectangle;
+
+/// An axis aligned ellipse with separate x and y radii.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Ellipse {
+    pub(crate) pos: IVec2,
+    pub(crate) radius: IVec2,
+    pub(crate) limits: Option<Rectangle>,
+}
+
+impl Ellipse {
+    /// Creates a new ellipse, `radius` holds the x and y radii.
+    pub const fn new(pos: IVec2, radius: IVec2) -> Self {
+        Self {
+            pos,
+            radius,
+            limits: None,
+        }
+    }
+
+    pub const fn new_with_limits(pos: IVec2, radius: IVec2, limits: Rectangle) -> Self {
+        Self {
+            pos,
+            radius,
+            limits: Some(limits),
+        }
+    }
+
+    /// Returns the bounds of the ellipse in its own coordinates, disregarding the limits.
+    pub fn bounds(&self) -> Rectangle {
+        let tl = self.pos - self.radius;
+        let br = self.pos + self.radius;
+        Rectangle::new(tl, br)
+    }
+
+    /// Returns the bounding box of the ellipse, respecting limits.
+    pub fn limited_bounding_box(&self) -> Rectangle {
+        if let Some(limits) = self.limits {
+            let tl = self.pos - self.radius;
+            let tl = tl.max(limits.tl);
+            let br = (self.pos + self.radius).min(limits.br);
+            Rectangle::new(tl, br)
+        } else {
+            self.bounds()
+        }
+    }
+
+    /// Return the center point of the ellipse scaled by a factor of 2
+    ///
+    /// Uses the same maths as `Circle::center_2x`, so an ellipse with equal radii covers
+    /// exactly the same pixels as the circle with that radius.
+    #[allow(dead_code)]
+    pub(crate) fn center_2x(&self) -> IVec2 {
+        let radius_2x = self.radius * 2 - IVec2::splat(1);
+        (self.pos - self.radius) * 2 + radius_2x
+    }
+    
+    /// Returns the threshold for this ellipses radii.
+    ///
+    /// The squared x and y distances to the doubled center are weighted by the radius of the
+    /// other axis, which reduces to the `Circle` threshold when both radii are equal.
+    #[allow(dead_code)]
+    pub(crate) fn threshold(&self) -> i64 {
+        let radius = self.radius.as_i64vec2();
+        let x = super::circle::diameter_to_threshold(self.radius.x * 2) as i64 * radius.y.pow(2);
+        let y = super::circle::diameter_to_threshold(self.radius.y * 2) as i64 * radius.x.pow(2);
+        x.min(y)
+    }
+    
+
+    /// Returns an iterator over the pixels of the ellipse.
+    pub fn pixel_iter(&self, outline: bool) -> EllipsePoints {
+        
+        EllipsePoints::new(self, outline)
+        
+    }
+
+    /// Returns an iterator over the pixels of the shape that lie inside `clip`, the same pixels
+    /// `pixel_iter` yields inside of it.
+    pub fn pixel_iter_clipped(&self, outline: bool, clip: crate::ivec2::Rectangle) -> EllipsePoints {
+        let mut ellipse = *self;
+        ellipse.limits = Some(crate::iters::common::clip_limits(ellipse.limits, clip));
+        EllipsePoints::new(&ellipse, outline)
+    }
+
+    /// Returns an iterator over the rows of the filled ellipse, yielding each row with its range
+    /// of columns.
+    pub fn span_iter(&self) -> EllipseSpans {
+        EllipseSpans::new(self)
+        }
+    
+
+    
+    pub fn as_ellipse_vec2(&self) -> crate::vec2::Ellipse {
+        crate::vec2::Ellipse {
+            pos: self.pos.as_vec2(),
+            radius: self.radius.as_vec2(),
+            limits: self.limits.map(|limits| limits.as_rectangle_vec2()),
+        }
+    }
+    
+
+    
+    pub fn as_ellipse_uvec2(&self) -> crate::uvec2::Ellipse {
+        crate::uvec2::Ellipse {
+            pos: self.pos.as_uvec2(),
+            radius: self.radius.as_uvec2(),
+            limits: self.limits.map(|limits| limits.as_rectangle_uvec2()),
+        }
+    }
+    
+}
+
+impl crate::Shape for Ellipse {
+    type Vector = IVec2;
+    type Scalar = i32;
+
+    fn position(&self) -> IVec2 {
+        self.pos
+    }
+
+    fn center(&self) -> IVec2 {
+        self.pos
+    }
+
+    fn contains(&self, coord: IVec2) -> bool {
+        let delta = (coord * 2 - self.center_2x()).as_i64vec2();
+        let radius = self.radius.as_i64vec2();
+        let distance = delta.x.pow(2) * radius.y.pow(2) + delta.y.pow(2) * radius.x.pow(2);
+
+        distance < self.threshold()
+        
+    }
+
+    /// Returns the pixels of the ellipse, disregarding the limits.
+    fn bounding_box(&self) -> crate::ivec2::Rectangle {
+        self.bounds()
+        }
+
+    fn fill_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(false)
+    }
+
+    fn outline_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(true)
+    }
+
+    fn fill_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(false, clip)
+    }
+
+    fn outline_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(true, clip)
+    }
+}
+
+impl crate::SignedDistance for Ellipse {
+    /// Returns an approximation of the distance, exact on the edge and along the axes.
+    fn signed_distance(&self, point: glam::Vec2) -> f32 {
+        let radius = self.radius.as_vec2();
+        if radius.min_element() <= 0.0 {
+            return f32::INFINITY;
+        }
+        let offset = point - self.pos.as_vec2();
+        let k0 = (offset / radius).length();
+        let k1 = (offset / (radius * radius)).length();
+        if k1 == 0.0 {
+            return -radius.min_element();
+        }
+        k0 * (k0 - 1.0) / k1
+    }
+
+    fn distance_bounds(&self) -> crate::ivec2::Rectangle {
+        let pos = self.pos.as_vec2();
+        let radius = self.radius.as_vec2();
+        crate::ivec2::Rectangle::new((pos - radius).floor().as_ivec2(), (pos + radius).ceil().as_ivec2())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Shape;
+
+    #[test]
+    fn test_contains() {
+        let ellipse = Ellipse::new(IVec2::new(10, 10), IVec2::new(6, 3));
+        assert!(ellipse.contains(IVec2::new(10, 10)));
+        assert!(ellipse.contains(IVec2::new(4, 9)));
+        assert!(ellipse.contains(IVec2::new(15, 10)));
+        assert!(!ellipse.contains(IVec2::new(16, 10)));
+        assert!(!ellipse.contains(IVec2::new(10, 13)));
+        assert!(!ellipse.contains(IVec2::new(4, 7)));
+    }
+}
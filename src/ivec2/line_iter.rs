@@ -1,25 +1,30 @@
-use glam::{IVec2, Vec2};
+use std::ops::Range;
+
+use glam::IVec2;
 use strum::EnumDiscriminants;
 
-use crate::ivec2::LineSegment;
+use crate::ivec2::{LineSegment, Rectangle};
 
 
 #[derive(Debug, Copy, Clone)]
 pub struct LineIter {
     impl_iter: LineIterImpl,
+    /// Number of pixels left to yield.
+    remaining: u32,
 }
 
 
 
 impl LineIter {
     pub fn new_from_segment(ls: LineSegment, iter_type: LineDrawAlgo) -> LineIter {
+        let delta = (ls.end - ls.start).abs();
         match iter_type {
             LineDrawAlgo::WalkGrid => {
                 let d = ls.end - ls.start;
                 let point = ls.start;
-                let i = Vec2::ZERO;
+                let i = IVec2::ZERO;
                 let sign = d.signum();
-                let n = d.abs().as_vec2();
+                let n = d.abs();
 
                 LineIter {
                     impl_iter: LineIterImpl::WalkGrid(WalkGridParams {
@@ -27,7 +32,21 @@ impl LineIter {
                         i,
                         sign,
                         n,
-                    })
+                    }),
+                    remaining: (delta.x + delta.y) as u32 + 1,
+                }
+            }
+            LineDrawAlgo::Supercover => {
+                let n = (ls.end - ls.start).abs();
+                let sign = (ls.end - ls.start).signum();
+                let params = SupercoverParams {
+                    front: SupercoverWalk::new(ls.start, sign, n),
+                    back: SupercoverWalk::new(ls.end, -sign, n),
+                };
+
+                LineIter {
+                    impl_iter: LineIterImpl::Supercover(params),
+                    remaining: (n.x + n.y) as u32 + 1 + params.corner_crossings(),
                 }
             }
             LineDrawAlgo::Bresenham => {
@@ -60,28 +79,388 @@ impl LineIter {
                         sx,
                         sy,
                         done: false,
-                    })
+                    }),
+                    remaining: delta.max_element() as u32 + 1,
                 }
             },
         }
     }
+
+    /// Creates an iterator over the pixels of the line that lie inside `clip`.
+    ///
+    /// The line is clipped before it is walked, Liang–Barsky style: the range of steps inside
+    /// `clip` is solved for directly and the iterator starts at the first of them, so pixels
+    /// outside of `clip` are never visited. The yielded pixels are exactly the pixels of the
+    /// unclipped line that lie inside `clip`.
+    pub fn new_from_segment_clipped(ls: LineSegment, iter_type: LineDrawAlgo, clip: &Rectangle) -> LineIter {
+        let mut iter = Self::new_from_segment(ls, iter_type);
+        let steps = match &mut iter.impl_iter {
+            LineIterImpl::WalkGrid(params) => params.clip(clip),
+            LineIterImpl::Bresenham(params) => params.clip(ls.start, clip),
+            LineIterImpl::Supercover(params) => params.clip(clip),
+        };
+        iter.remaining = steps;
+        iter
+    }
 }
 impl Iterator for LineIter {
     type Item = IVec2;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
         self.impl_iter.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+}
+
+/// Yields the pixels of the line in reverse order, from the end to the start.
+///
+/// The pixels are the same the iterator yields forwards, ties are broken the same way even
+/// though the line is walked from the other end.
+impl DoubleEndedIterator for LineIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        match &mut self.impl_iter {
+            LineIterImpl::WalkGrid(params) => Some(params.point_after(self.remaining as i64)),
+            LineIterImpl::Bresenham(params) => Some(params.point_after(self.remaining as i64)),
+            LineIterImpl::Supercover(params) => params.next_back(),
+        }
+    }
+}
+
+impl ExactSizeIterator for LineIter {}
+
+/// Returns the first and last step `t` for which `start + sign * t` lies in `range`.
+fn steps_in_range(start: i32, sign: i32, range: Range<i32>) -> (i64, i64) {
+    let (start, first, last) = (start as i64, range.start as i64, range.end as i64 - 1);
+    if sign < 0 {
+        (start - last, start - first)
+    } else {
+        (first - start, last - start)
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
 pub struct WalkGridParams {
     point: IVec2,
-    i: Vec2,
+    i: IVec2,
     sign: IVec2,
-    n: Vec2,
+    n: IVec2,
 }
 
+impl WalkGridParams {
+    /// Returns `true` if the next step is along `x`.
+    ///
+    /// Compares `(0.5 + i.x) / n.x < (0.5 + i.y) / n.y` without dividing.
+    fn steps_x(&self) -> bool {
+        self.next_step() == std::cmp::Ordering::Less
+    }
+
+    /// Number of steps along `y` taken before step `j` along `x`.
+    fn y_steps_before(&self, j: i64) -> i64 {
+        let n = self.n.as_i64vec2();
+        if n.x == 0 {
+            return n.y;
+        }
+        (((2 * j + 1) * n.y - n.x).div_euclid(2 * n.x) + 1).clamp(0, n.y)
+    }
+
+    /// Number of steps along `x` taken before step `j` along `y`.
+    fn x_steps_before(&self, j: i64) -> i64 {
+        let n = self.n.as_i64vec2();
+        if n.y == 0 {
+            return n.x;
+        }
+        (((2 * j + 1) * n.x - 1 - n.y).div_euclid(2 * n.y) + 1).clamp(0, n.x)
+    }
+
+    /// Returns the ordering of `(0.5 + i.x) / n.x` and `(0.5 + i.y) / n.y`, `Equal` if the next
+    /// step passes exactly through a pixel corner.
+    fn next_step(&self) -> std::cmp::Ordering {
+        let (i, n) = (self.i.as_i64vec2(), self.n.as_i64vec2());
+        ((2 * i.x + 1) * n.y).cmp(&((2 * i.y + 1) * n.x))
+    }
+
+    /// Returns the pixel `offset` steps after the current one.
+    ///
+    /// Step `j` along `x` is step `j + y_steps_before(j)` of the walk, so the number of steps
+    /// along `x` before any step of the walk is found with a binary search.
+    fn point_after(&self, offset: i64) -> IVec2 {
+        let step = (self.i.x + self.i.y) as i64 + offset;
+        let (mut low, mut high) = (0, self.n.x as i64);
+        while low < high {
+            let mid = (low + high) / 2;
+            if mid + self.y_steps_before(mid) < step {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        let i = IVec2::new(low as i32, (step - low) as i32);
+        self.point + (i - self.i) * self.sign
+    }
+
+    /// Moves to the first pixel inside `clip` and returns the number of pixels inside it.
+    fn clip(&mut self, clip: &Rectangle) -> u32 {
+        let n = self.n.as_i64vec2();
+        let (x_first, x_last) = steps_in_range(self.point.x, self.sign.x, clip.columns());
+        let (y_first, y_last) = steps_in_range(self.point.y, self.sign.y, clip.rows());
+        let (x_first, x_last) = (x_first.max(0), x_last.min(n.x));
+        let (y_first, y_last) = (y_first.max(0), y_last.min(n.y));
+        if x_first > x_last || y_first > y_last {
+            return 0;
+        }
+
+        // the first pixel that reached both `x_first` and `y_first`
+        let mut entry = (0, 0);
+        if x_first > 0 {
+            entry = (x_first, self.y_steps_before(x_first - 1));
+        }
+        if y_first > 0 {
+            let entry_y = (self.x_steps_before(y_first - 1), y_first);
+            if entry_y.0 + entry_y.1 > entry.0 + entry.1 {
+                entry = entry_y;
+            }
+        }
+
+        // the last pixel before passing `x_last` or `y_last`
+        let total = n.x + n.y;
+        let exit_x = if x_last < n.x { x_last + self.y_steps_before(x_last) } else { total };
+        let exit_y = if y_last < n.y { y_last + self.x_steps_before(y_last) } else { total };
+        let exit = exit_x.min(exit_y);
+
+        let first = entry.0 + entry.1;
+        if first > exit {
+            return 0;
+        }
+
+        let i = IVec2::new(entry.0 as i32, entry.1 as i32);
+        self.point += i * self.sign;
+        self.i = i;
+
+        (exit - first + 1) as u32
+    }
+}
+
+/// Walks the grid like `WalkGrid`, but steps diagonally where the line passes exactly through a
+/// pixel corner, yielding both pixels next to the corner first.
+#[derive(Debug, Copy, Clone)]
+pub struct SupercoverParams {
+    front: SupercoverWalk,
+    /// Walks the line from the end, used when iterating backwards. The line is symmetric, so
+    /// the walk from the end yields the same pixels in reverse order.
+    back: SupercoverWalk,
+}
+
+impl SupercoverParams {
+    /// Returns the number of pixel corners the line passes through exactly.
+    ///
+    /// With `n = g * (a, b)` for coprime `a` and `b`, the line crosses `g` corners if `a` and `b`
+    /// are both odd and none otherwise.
+    fn corner_crossings(&self) -> u32 {
+        let n = self.front.walk.n;
+        let g = gcd(n.x, n.y);
+        if g == 0 || (n.x / g) % 2 == 0 || (n.y / g) % 2 == 0 {
+            return 0;
+        }
+        g as u32
+    }
+
+    /// Returns `true` if the line passes through a pixel corner right after step `i` from the
+    /// start.
+    fn is_corner(&self, i: IVec2) -> bool {
+        let walk = WalkGridParams { i, ..self.front.walk };
+        i.cmpge(IVec2::ZERO).all() && i.cmplt(walk.n).all() && walk.next_step() == std::cmp::Ordering::Equal
+    }
+
+    /// Returns the step from the start after which the line passes through corner `m`.
+    ///
+    /// With `n = g * (a, b)`, corner `m` is passed after step `(a * m + (a - 1) / 2, b * m + (b - 1) / 2)`.
+    fn corner_step(&self, m: i64) -> IVec2 {
+        let (a, b) = self.corner_period();
+        IVec2::new((a * m + (a - 1) / 2) as i32, (b * m + (b - 1) / 2) as i32)
+    }
+
+    fn corner_period(&self) -> (i64, i64) {
+        let g = self.corner_crossings().max(1) as i64;
+        (self.front.walk.n.x as i64 / g, self.front.walk.n.y as i64 / g)
+    }
+
+    /// Returns the first and last corner whose side pixel along `x` lies inside `clip`.
+    fn corners_with_x_side_in(&self, clip: &Rectangle) -> (i64, i64) {
+        let walk = self.front.walk;
+        let g = self.corner_crossings() as i64;
+        if g == 0 {
+            return (0, -1);
+        }
+        let (a, b) = self.corner_period();
+        let (x_first, x_last) = steps_in_range(walk.point.x, walk.sign.x, clip.columns());
+        let (y_first, y_last) = steps_in_range(walk.point.y, walk.sign.y, clip.rows());
+        let (x_offset, y_offset) = ((a + 1) / 2, (b - 1) / 2);
+
+        let first = ceil_div(x_first - x_offset, a).max(ceil_div(y_first - y_offset, b)).max(0);
+        let last = (x_last - x_offset).div_euclid(a).min((y_last - y_offset).div_euclid(b)).min(g - 1);
+        (first, last)
+    }
+
+    /// Moves both walks to the ends of the part of the line inside `clip` and returns the number
+    /// of pixels inside it.
+    ///
+    /// The line is the `WalkGrid` line, which passes each corner through its side pixel along
+    /// `y`, plus the side pixels along `x`. The `WalkGrid` part is clipped like `WalkGrid` and
+    /// the side pixels along `x` inside `clip` are counted per corner. Only the corners at both
+    /// ends of the clipped part can have side pixels outside `clip`, so the walks drop those
+    /// instead of skipping pixels.
+    fn clip(&mut self, clip: &Rectangle) -> u32 {
+        let (start, sign, n) = (self.front.walk.point, self.front.walk.sign, self.front.walk.n);
+        let (first_corner, last_corner) = self.corners_with_x_side_in(clip);
+        let x_sides = (last_corner - first_corner + 1).max(0) as u32;
+        self.front.clip = Some(*clip);
+        self.back.clip = Some(*clip);
+
+        let mut grid = self.front.walk;
+        let steps = grid.clip(clip);
+        if steps == 0 {
+            // two side pixels inside `clip` would put the pixel between them inside as well
+            if x_sides > 0 {
+                let side = start + sign * (self.corner_step(first_corner) + IVec2::X);
+                self.front.sides = [Some(side), None];
+                self.back.sides = [Some(side), None];
+            }
+            return x_sides;
+        }
+
+        // a first pixel next to a corner is preceded by the side pixels of that corner
+        let entry = grid.i;
+        let corner = [entry - IVec2::Y, entry - IVec2::ONE].into_iter().find(|&i| self.is_corner(i));
+        if let Some(corner) = corner {
+            self.front.walk.i = corner + IVec2::ONE;
+            self.front.walk.point = start + sign * self.front.walk.i;
+            self.front.sides = self.front.corner_sides(start + sign * corner);
+        } else {
+            self.front.walk.i = entry;
+            self.front.walk.point = grid.point;
+        }
+
+        // a last pixel next to a corner is followed by the side pixels of that corner
+        let last = grid.point_after(steps as i64 - 1);
+        let exit = (last - start) * sign;
+        let corner = [exit, exit - IVec2::Y].into_iter().find(|&i| self.is_corner(i));
+        if let Some(corner) = corner {
+            self.back.walk.i = n - corner;
+            self.back.walk.point = start + sign * corner;
+            self.back.sides = self.back.corner_sides(start + sign * (corner + IVec2::ONE));
+        } else {
+            self.back.walk.i = n - exit;
+            self.back.walk.point = last;
+        }
+
+        steps + x_sides
+    }
+}
+
+impl Iterator for SupercoverParams {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.front.next()
+    }
+}
+
+impl DoubleEndedIterator for SupercoverParams {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.back.next()
+    }
+}
+
+/// A walk along the grid from one end of a supercover line.
+#[derive(Debug, Copy, Clone)]
+struct SupercoverWalk {
+    walk: WalkGridParams,
+    /// Pixels next to the corner that was just passed, yielded before the pixel after it.
+    sides: [Option<IVec2>; 2],
+    /// Side pixels outside of the clip rectangle are dropped.
+    clip: Option<Rectangle>,
+}
+
+impl SupercoverWalk {
+    fn new(point: IVec2, sign: IVec2, n: IVec2) -> Self {
+        Self {
+            walk: WalkGridParams {
+                point,
+                i: IVec2::ZERO,
+                sign,
+                n,
+            },
+            sides: [None; 2],
+            clip: None,
+        }
+    }
+
+    /// Returns the pixels next to the corner after `point` that lie inside the clip rectangle.
+    fn corner_sides(&self, point: IVec2) -> [Option<IVec2>; 2] {
+        let inside = |p: &IVec2| match self.clip {
+            Some(clip) => clip.columns().contains(&p.x) && clip.rows().contains(&p.y),
+            None => true,
+        };
+        [
+            Some(point + IVec2::new(self.walk.sign.x, 0)).filter(inside),
+            Some(point + IVec2::new(0, self.walk.sign.y)).filter(inside),
+        ]
+    }
+}
+
+impl Iterator for SupercoverWalk {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(side) = self.sides.iter_mut().find_map(|side| side.take()) {
+            return Some(side);
+        }
+
+        let walk = &mut self.walk;
+        if walk.i.x > walk.n.x || walk.i.y > walk.n.y {
+            return None;
+        }
+        let point = walk.point;
+
+        match walk.next_step() {
+            std::cmp::Ordering::Less => {
+                walk.point.x += walk.sign.x;
+                walk.i.x += 1;
+            }
+            std::cmp::Ordering::Greater => {
+                walk.point.y += walk.sign.y;
+                walk.i.y += 1;
+            }
+            std::cmp::Ordering::Equal => {
+                walk.point += walk.sign;
+                walk.i += IVec2::ONE;
+                self.sides = self.corner_sides(point);
+            }
+        }
+
+        Some(point)
+    }
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
 
 #[derive(Debug, Copy, Clone, Default)]
 pub struct BresenhamParams {
@@ -96,6 +475,76 @@ pub struct BresenhamParams {
     done: bool,
 }
 
+impl BresenhamParams {
+    /// Returns the pixel `offset` steps after the current one, using the same closed form as
+    /// `clip`.
+    fn point_after(&self, offset: i64) -> IVec2 {
+        let x_major = self.dx > self.dy;
+        let (dx, dy) = (self.dx as i64, self.dy as i64);
+        let (major, minor) = if x_major { (dx, dy) } else { (dy, dx) };
+        let start = self.end - IVec2::new(self.sx * self.dx, self.sy * self.dy);
+
+        let taken = if x_major { self.current_x - start.x } else { self.current_y - start.y };
+        let steps = taken.abs() as i64 + offset;
+        let minor_steps = if major == 0 { 0 } else { (steps * minor + major - 1 - major / 2).div_euclid(major) };
+        let (steps_x, steps_y) = if x_major { (steps, minor_steps) } else { (minor_steps, steps) };
+
+        start + IVec2::new(self.sx * steps_x as i32, self.sy * steps_y as i32)
+    }
+
+    /// Moves to the first pixel inside `clip` and returns the number of pixels inside it.
+    ///
+    /// After `k` steps along the major axis the minor axis has moved
+    /// `(k * minor + major - 1 - major / 2) / major` pixels, which gives the pixel and error
+    /// term of any step without walking the line.
+    fn clip(&mut self, start: IVec2, clip: &Rectangle) -> u32 {
+        let x_major = self.dx > self.dy;
+        let (dx, dy) = (self.dx as i64, self.dy as i64);
+        let (major, minor) = if x_major { (dx, dy) } else { (dy, dx) };
+        let offset = major - 1 - major / 2;
+
+        let (x_first, x_last) = steps_in_range(start.x, self.sx, clip.columns());
+        let (y_first, y_last) = steps_in_range(start.y, self.sy, clip.rows());
+        let ((first, last), (minor_first, minor_last)) = if x_major {
+            ((x_first, x_last), (y_first, y_last))
+        } else {
+            ((y_first, y_last), (x_first, x_last))
+        };
+        let mut first = first.max(0);
+        let mut last = last.min(major);
+
+        // steps whose minor position lies in `minor_first..=minor_last`
+        if minor == 0 {
+            if minor_first > 0 || minor_last < 0 {
+                return 0;
+            }
+        } else {
+            first = first.max(ceil_div(minor_first * major - offset, minor));
+            last = last.min(((minor_last + 1) * major - offset - 1).div_euclid(minor));
+        }
+        if first > last {
+            return 0;
+        }
+
+        let minor_steps = if major == 0 { 0 } else { (first * minor + offset).div_euclid(major) };
+        let (steps_x, steps_y, error) = if x_major {
+            (first, minor_steps, major / 2 - first * dy + minor_steps * dx)
+        } else {
+            (minor_steps, first, -(major / 2) + first * dx - minor_steps * dy)
+        };
+
+        self.current_x = start.x + self.sx * steps_x as i32;
+        self.current_y = start.y + self.sy * steps_y as i32;
+        self.error = error as i32;
+
+        (last - first + 1) as u32
+    }
+}
+
+fn ceil_div(a: i64, b: i64) -> i64 {
+    -(-a).div_euclid(b)
+}
+
 
 
 #[derive(EnumDiscriminants, Debug, Copy, Clone)]
@@ -104,6 +553,8 @@ pub struct BresenhamParams {
 pub(crate) enum LineIterImpl {
     WalkGrid(WalkGridParams),
     Bresenham(BresenhamParams),
+    /// Every pixel the line touches, including both pixels where it passes through a corner.
+    Supercover(SupercoverParams),
 }
 
 impl Iterator for LineIterImpl {
@@ -115,12 +566,12 @@ impl Iterator for LineIterImpl {
                 if params.i.x <= params.n.x && params.i.y <= params.n.y {
                     let point = params.point;
         
-                    if (0.5 + params.i.x) / params.n.x < (0.5 + params.i.y) / params.n.y {
+                    if params.steps_x() {
                         params.point.x += params.sign.x;
-                        params.i.x += 1.0;
+                        params.i.x += 1;
                     } else {
                         params.point.y += params.sign.y;
-                        params.i.y += 1.0;
+                        params.i.y += 1;
                     }
         
                     Some(point)
@@ -153,6 +604,7 @@ impl Iterator for LineIterImpl {
 
                 Some(current)
             }
+            LineIterImpl::Supercover(params) => params.next(),
         }
     }
 }
@@ -231,9 +683,10 @@ mod test {
         let expected = vec![
             IVec2::new(0, 0),
             IVec2::new(0, 1),
-            IVec2::new(1, 2),
+            IVec2::new(0, 2),
             IVec2::new(1, 3),
             IVec2::new(1, 4),
+            IVec2::new(1, 5),
         ];
 
         assert_eq!(iter.collect::<Vec<IVec2>>(), expected);
@@ -247,17 +700,134 @@ mod test {
 
         let expected = vec![
             IVec2::new(0, 0),
-            IVec2::new(-1, 1),
-            IVec2::new(-2, 2),
-            IVec2::new(-3, 3),
-            IVec2::new(-4, 4),
-            IVec2::new(-5, 5),
+            IVec2::new(0, 1),
+            IVec2::new(-1, 2),
+            IVec2::new(-1, 3),
+            IVec2::new(-2, 4),
+            IVec2::new(-2, 5),
         ];
 
         assert_eq!(iter.collect::<Vec<IVec2>>(), expected);
 
     }    
 
+    /// Clipping a line yields the same pixels as filtering the unclipped line.
+    #[test]
+    fn test_clipped_matches_filtered() {
+        let clip = Rectangle::new(IVec2::new(-3, -2), IVec2::new(5, 4));
+        let inside = |p: &IVec2| clip.columns().contains(&p.x) && clip.rows().contains(&p.y);
+
+        for algo in [LineDrawAlgo::Bresenham, LineDrawAlgo::WalkGrid, LineDrawAlgo::Supercover] {
+            for start in [IVec2::new(-9, -7), IVec2::new(0, 0), IVec2::new(7, -1), IVec2::new(2, 9)] {
+                for end_x in -10..11 {
+                    for end_y in -10..11 {
+                        let ls = LineSegment::new(start, IVec2::new(end_x, end_y));
+                        let expected: Vec<IVec2> = LineIter::new_from_segment(ls, algo).filter(inside).collect();
+                        let clipped: Vec<IVec2> = LineIter::new_from_segment_clipped(ls, algo, &clip).collect();
+
+                        assert_eq!(clipped, expected, "{:?} {:?}", algo, ls);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Iterating from both ends yields the same pixels as iterating forwards, with an exact length
+    /// throughout.
+    #[test]
+    fn test_double_ended() {
+        let clip = Rectangle::new(IVec2::new(-3, -2), IVec2::new(5, 4));
+
+        for algo in [LineDrawAlgo::Bresenham, LineDrawAlgo::WalkGrid, LineDrawAlgo::Supercover] {
+            for start in [IVec2::new(-9, -7), IVec2::new(0, 0), IVec2::new(7, -1)] {
+                for end_x in -10..11 {
+                    for end_y in -10..11 {
+                        let ls = LineSegment::new(start, IVec2::new(end_x, end_y));
+                        for iter in [LineIter::new_from_segment(ls, algo), LineIter::new_from_segment_clipped(ls, algo, &clip)] {
+                            let points: Vec<IVec2> = iter.collect();
+                            let mut reversed: Vec<IVec2> = iter.rev().collect();
+                            reversed.reverse();
+                            assert_eq!(reversed, points, "{:?} {:?}", algo, ls);
+
+                            let mut iter = iter;
+                            let (mut front, mut back) = (Vec::new(), Vec::new());
+                            for len in (0..=points.len()).rev() {
+                                assert_eq!(iter.len(), len);
+                                if len % 2 == 0 {
+                                    front.extend(iter.next());
+                                } else {
+                                    back.extend(iter.next_back());
+                                }
+                            }
+                            assert_eq!(iter.next_back(), None);
+                            front.extend(back.into_iter().rev());
+                            assert_eq!(front, points, "{:?} {:?}", algo, ls);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_supercover_corners() {
+        let ls = LineSegment::new(IVec2::new(0, 0), IVec2::new(2, 2));
+        let points: Vec<IVec2> = LineIter::new_from_segment(ls, LineDrawAlgo::Supercover).collect();
+
+        assert_eq!(
+            points,
+            vec![
+                IVec2::new(0, 0),
+                IVec2::new(1, 0),
+                IVec2::new(0, 1),
+                IVec2::new(1, 1),
+                IVec2::new(2, 1),
+                IVec2::new(1, 2),
+                IVec2::new(2, 2),
+            ]
+        );
+
+        // passes through the corner between (1, 0), (2, 0), (1, 1) and (2, 1)
+        let ls = LineSegment::new(IVec2::new(3, 1), IVec2::new(0, 0));
+        let points: Vec<IVec2> = LineIter::new_from_segment(ls, LineDrawAlgo::Supercover).collect();
+        assert_eq!(
+            points,
+            vec![
+                IVec2::new(3, 1),
+                IVec2::new(2, 1),
+                IVec2::new(1, 1),
+                IVec2::new(2, 0),
+                IVec2::new(1, 0),
+                IVec2::new(0, 0),
+            ]
+        );
+    }
+
+    /// The supercover is the walk grid line plus the second pixel at every corner crossing.
+    #[test]
+    fn test_supercover_contains_walk_grid() {
+        for end_x in -12..13 {
+            for end_y in -12..13 {
+                let ls = LineSegment::new(IVec2::new(0, 0), IVec2::new(end_x, end_y));
+                let walk_grid: Vec<IVec2> = LineIter::new_from_segment(ls, LineDrawAlgo::WalkGrid).collect();
+                let iter = LineIter::new_from_segment(ls, LineDrawAlgo::Supercover);
+                let count = iter.remaining as usize;
+                let supercover: Vec<IVec2> = iter.collect();
+
+                assert_eq!(supercover.len(), count, "{:?}", ls);
+                assert!(walk_grid.iter().all(|p| supercover.contains(p)), "{:?}", ls);
+                assert_eq!(supercover.last(), Some(&ls.end));
+
+                // every pixel touches the ideal line through the pixel centers
+                let (start, end) = (ls.start.as_vec2(), ls.end.as_vec2());
+                for p in &supercover {
+                    let distance = crate::iters::sdf_iter::segment_distance(start, end, p.as_vec2());
+                    assert!(distance <= std::f32::consts::FRAC_1_SQRT_2 + 1e-5, "{:?} {:?}", ls, p);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_bresenham_line_iter_impl_diag() {
         let ls = LineSegment::new(IVec2::new(0, 0), IVec2::new(3, 3));
@@ -0,0 +1,188 @@
+use glam::IVec2;
+use glam::Vec2;
+use crate::iters::polygon_iter::{FillRule, PolygonPixels, PolygonSpans};
+use super::rectangle::Rectangle;
+
+/// A closed polygon built from a list of vertices.
+///
+/// The last vertex is connected back to the first one, self intersecting outlines are filled
+/// according to the polygons `FillRule`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Polygon {
+    pub(crate) vertices: Vec<IVec2>,
+    pub(crate) fill_rule: FillRule,
+    pub(crate) limits: Option<Rectangle>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<IVec2>, fill_rule: FillRule) -> Self {
+        Self {
+            vertices,
+            fill_rule,
+            limits: None,
+        }
+    }
+
+    pub fn new_with_limits(vertices: Vec<IVec2>, fill_rule: FillRule, limits: Rectangle) -> Self {
+        Self {
+            vertices,
+            fill_rule,
+            limits: Some(limits),
+        }
+    }
+
+    pub fn vertices(&self) -> &[IVec2] {
+        &self.vertices
+    }
+
+    pub fn fill_rule(&self) -> FillRule {
+        self.fill_rule
+    }
+
+    /// Returns the bounds of the vertices of the polygon, disregarding the limits.
+    pub fn bounds(&self) -> Rectangle {
+        Rectangle::from_points(self.vertices.clone())
+    }
+
+    /// Returns the bounding box of the polygon, respecting limits.
+    pub fn limited_bounding_box(&self) -> Rectangle {
+        let bounds = self.bounds();
+        if let Some(limits) = self.limits {
+            let tl = bounds.tl.max(limits.tl);
+            let br = bounds.br.min(limits.br);
+            Rectangle::new(tl, br)
+        } else {
+            bounds
+        }
+    }
+
+    /// Returns an iterator over the pixels of the polygon.
+    pub fn pixel_iter(&self, outline: bool) -> PolygonPixels {
+        PolygonPixels::new(self.vertices_vec2(), self.fill_rule, self.limits_ivec2(), outline)
+    }
+
+    /// Returns an iterator over the pixels of the shape that lie inside `clip`, the same pixels
+    /// `pixel_iter` yields inside of it.
+    pub fn pixel_iter_clipped(&self, outline: bool, clip: crate::ivec2::Rectangle) -> PolygonPixels {
+        let limits = crate::iters::common::clip_limits(self.limits_ivec2(), clip);
+        PolygonPixels::new(self.vertices_vec2(), self.fill_rule, Some(limits), outline)
+    }
+
+    /// Returns an iterator over the horizontal runs of the filled polygon, yielding each run with
+    /// its row. Rows can contain several runs.
+    pub fn span_iter(&self) -> PolygonSpans {
+        PolygonSpans::new(self.vertices_vec2(), self.fill_rule, self.limits_ivec2())
+    }
+
+    fn limits_ivec2(&self) -> Option<crate::ivec2::Rectangle> {
+        self.limits
+        }
+
+    /// Returns the smallest and the largest coordinates of the vertices.
+    fn vertex_bounds(&self) -> Option<(Vec2, Vec2)> {
+        let vertices = self.vertices_vec2();
+        let first = *vertices.first()?;
+        Some(vertices.iter().fold((first, first), |(min, max), v| (min.min(*v), max.max(*v))))
+    }
+
+    fn vertices_vec2(&self) -> Vec<Vec2> {
+        self.vertices.iter().map(|v| v.as_vec2()).collect()
+        }
+    
+    
+    pub fn as_polygon_vec2(&self) -> crate::vec2::Polygon {
+        crate::vec2::Polygon::new(self.vertices_vec2(), self.fill_rule)
+    }
+    
+    
+    pub fn as_polygon_uvec2(&self) -> crate::uvec2::Polygon {
+        crate::uvec2::Polygon::new(self.vertices.iter().map(|v| v.as_uvec2()).collect(), self.fill_rule)
+    }
+    
+}
+
+impl crate::Shape for Polygon {
+    type Vector = IVec2;
+    type Scalar = i32;
+
+    fn position(&self) -> IVec2 {
+        self.bounds().tl
+    }
+
+    /// Returns the average of the polygons vertices.
+    fn center(&self) -> IVec2 {
+        if self.vertices.is_empty() {
+            return IVec2::ZERO;
+        }
+        let sum = self.vertices.iter().fold(glam::I64Vec2::ZERO, |sum, v| sum + v.as_i64vec2());
+        (sum / self.vertices.len() as i64).as_ivec2()
+        }
+
+    /// Returns `true` if the pixel at `coord` is filled, using the polygons fill rule.
+    fn contains(&self, coord: IVec2) -> bool {
+        crate::iters::polygon_iter::contains(&self.vertices_vec2(), self.fill_rule, coord.as_vec2())
+    }
+
+    /// Returns the rows and columns whose pixel centers lie between the vertices, disregarding
+    /// the limits.
+    fn bounding_box(&self) -> crate::ivec2::Rectangle {
+        let Some((min, max)) = self.vertex_bounds() else {
+            return crate::ivec2::Rectangle::default();
+        };
+        let half = Vec2::splat(0.5);
+        crate::ivec2::Rectangle::new((min - half).ceil().as_ivec2(), (max - half).ceil().as_ivec2())
+    }
+
+    fn fill_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(false)
+    }
+
+    fn outline_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(true)
+    }
+
+    fn fill_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(false, clip)
+    }
+
+    fn outline_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(true, clip)
+    }
+}
+
+impl crate::SignedDistance for Polygon {
+    /// Returns the distance to the closest edge, negative inside according to the fill rule.
+    fn signed_distance(&self, point: Vec2) -> f32 {
+        let vertices = self.vertices_vec2();
+        let inside = crate::iters::polygon_iter::contains(&vertices, self.fill_rule, point - Vec2::splat(0.5));
+        crate::iters::sdf_iter::outline_distance(&vertices, inside, point)
+    }
+
+    fn distance_bounds(&self) -> crate::ivec2::Rectangle {
+        let Some((min, max)) = self.vertex_bounds() else {
+            return crate::ivec2::Rectangle::default();
+        };
+        crate::ivec2::Rectangle::new(min.floor().as_ivec2(), max.ceil().as_ivec2())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Shape;
+
+    #[test]
+    fn test_contains() {
+        let polygon = Polygon::new(vec![
+            IVec2::new(0, 0),
+            IVec2::new(4, 0),
+            IVec2::new(0, 4),
+        ], FillRule::NonZero);
+        assert!(polygon.contains(IVec2::new(0, 0)));
+        assert!(polygon.contains(IVec2::new(2, 0)));
+        assert!(!polygon.contains(IVec2::new(3, 0)));
+        assert!(!polygon.contains(IVec2::new(2, 2)));
+        assert_eq!(polygon.pixel_iter(false).count(), 6);
+    }
+}
@@ -0,0 +1,138 @@
+use glam::IVec2;
+use glam::Vec2;
+use crate::iters::triangle_iter::{TrianglePixels, TriangleSpans};
+
+/// A triangle, filled following the top-left rule.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Triangle {
+    pub a: IVec2,
+    pub b: IVec2,
+    pub c: IVec2,
+}
+
+impl Triangle {
+    pub const fn new(a: IVec2, b: IVec2, c: IVec2) -> Self {
+        Self { a, b, c }
+    }
+
+    pub fn vertices(&self) -> [IVec2; 3] {
+        [self.a, self.b, self.c]
+    }
+
+    /// Returns the pixel bounding box of the triangle.
+    pub fn bounding_box(&self) -> crate::ivec2::Rectangle {
+        let [a, b, c] = self.vertices_vec2();
+        crate::ivec2::Rectangle::new(
+            a.min(b).min(c).floor().as_ivec2(),
+            a.max(b).max(c).ceil().as_ivec2(),
+        )
+    }
+
+    /// Returns an iterator over the pixels of the triangle.
+    pub fn pixel_iter(&self, outline: bool) -> TrianglePixels {
+        TrianglePixels::new(self.vertices_vec2(), None, outline)
+    }
+
+    /// Returns an iterator over the pixels of the shape that lie inside `clip`, the same pixels
+    /// `pixel_iter` yields inside of it.
+    pub fn pixel_iter_clipped(&self, outline: bool, clip: crate::ivec2::Rectangle) -> TrianglePixels {
+        TrianglePixels::new(self.vertices_vec2(), Some(clip), outline)
+    }
+
+    /// Returns an iterator over the rows of the filled triangle, yielding each row with its range
+    /// of columns.
+    pub fn span_iter(&self) -> TriangleSpans {
+        TriangleSpans::new(self.vertices_vec2(), None)
+    }
+
+    fn vertices_vec2(&self) -> [Vec2; 3] {
+        self.vertices().map(|v| v.as_vec2())
+        }
+    
+    
+    pub fn as_triangle_vec2(&self) -> crate::vec2::Triangle {
+        crate::vec2::Triangle::new(self.a.as_vec2(), self.b.as_vec2(), self.c.as_vec2())
+    }
+    
+    
+    pub fn as_triangle_uvec2(&self) -> crate::uvec2::Triangle {
+        crate::uvec2::Triangle::new(self.a.as_uvec2(), self.b.as_uvec2(), self.c.as_uvec2())
+    }
+    
+}
+
+impl crate::Shape for Triangle {
+    type Vector = IVec2;
+    type Scalar = i32;
+
+    fn position(&self) -> IVec2 {
+        self.a.min(self.b).min(self.c)
+    }
+
+    /// Returns the centroid of the triangle.
+    fn center(&self) -> IVec2 {
+        (self.a + self.b + self.c) / 3
+    }
+
+    /// Returns `true` if the pixel at `coord` is filled, using the top-left rule.
+    fn contains(&self, coord: IVec2) -> bool {
+        crate::iters::triangle_iter::contains(self.vertices_vec2(), coord)
+    }
+
+    fn bounding_box(&self) -> crate::ivec2::Rectangle {
+        Triangle::bounding_box(self)
+    }
+
+    fn fill_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(false)
+    }
+
+    fn outline_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(true)
+    }
+
+    fn fill_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(false, clip)
+    }
+
+    fn outline_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(true, clip)
+    }
+}
+
+impl crate::SignedDistance for Triangle {
+    fn signed_distance(&self, point: Vec2) -> f32 {
+        let vertices = self.vertices_vec2();
+        let inside = crate::iters::polygon_iter::contains(
+            &vertices,
+            crate::iters::polygon_iter::FillRule::NonZero,
+            point - Vec2::splat(0.5),
+        );
+        crate::iters::sdf_iter::outline_distance(&vertices, inside, point)
+    }
+
+    fn distance_bounds(&self) -> crate::ivec2::Rectangle {
+        self.bounding_box()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Shape;
+
+    #[test]
+    fn test_contains() {
+        let triangle = Triangle::new(
+            IVec2::new(0, 0),
+            IVec2::new(4, 0),
+            IVec2::new(0, 4),
+        );
+        assert!(triangle.contains(IVec2::new(0, 0)));
+        assert!(triangle.contains(IVec2::new(2, 0)));
+        assert!(!triangle.contains(IVec2::new(3, 0)));
+        assert!(!triangle.contains(IVec2::new(2, 2)));
+        assert_eq!(triangle.bounding_box(), crate::ivec2::Rectangle::new(IVec2::new(0, 0), IVec2::new(4, 4)));
+    }
+}
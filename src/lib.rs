@@ -7,18 +7,244 @@ pub mod ivec2;
 pub mod uvec2;
 pub mod vec2;
 pub mod iters;
+pub mod buffer;
+pub mod color;
+pub mod geometry;
 
 // pub use circle::*;
-use glam::IVec2;
+use glam::{IVec2, Vec2};
+use iters::sdf_iter::SdfPixels;
 // pub use rectangle::*;
 
 
 
 
-pub trait Shape<T> {
-    fn position(&self) -> T;
-    fn center(&self) -> T;
-    fn contains(&self, coord: T) -> bool;
-    fn pixel_iter(&self, outline: bool) -> impl Iterator<Item = IVec2>;
+/// A shape made of pixels.
+///
+/// Implemented by the shapes of each of the `ivec2`, `uvec2` and `vec2` modules. The pixels of a
+/// shape are always `IVec2`s, whatever the vector type of its coordinates.
+pub trait Shape {
+    /// Vector type of the coordinates of the shape, like `glam::IVec2`.
+    type Vector;
+    /// Scalar type of the components of `Vector`, like `i32`.
+    type Scalar;
+
+    fn position(&self) -> Self::Vector;
+    fn center(&self) -> Self::Vector;
+    fn contains(&self, coord: Self::Vector) -> bool;
+
+    /// Returns a rectangle containing all pixels of the shape.
+    fn bounding_box(&self) -> ivec2::Rectangle;
+
+    /// Returns an iterator over the pixels of the filled shape.
+    fn fill_iter(&self) -> impl Iterator<Item = IVec2>;
+
+    /// Returns an iterator over the pixels of the outline of the shape.
+    fn outline_iter(&self) -> impl Iterator<Item = IVec2>;
+
+    /// Returns an iterator over the pixels of the filled shape that lie inside `clip`.
+    ///
+    /// The pixels are the same `fill_iter` yields inside `clip`, in the same order. The default
+    /// implementation filters `fill_iter`, shapes that can skip the clipped pixels override it.
+    fn fill_iter_clipped(&self, clip: ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        let (columns, rows) = (clip.columns(), clip.rows());
+        self.fill_iter()
+            .filter(move |point| columns.contains(&point.x) && rows.contains(&point.y))
+    }
+
+    /// Returns an iterator over the pixels of the outline that lie inside `clip`, see
+    /// `fill_iter_clipped`.
+    fn outline_iter_clipped(&self, clip: ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        let (columns, rows) = (clip.columns(), clip.rows());
+        self.outline_iter()
+            .filter(move |point| columns.contains(&point.x) && rows.contains(&point.y))
+    }
+}
+
+/// Object safe companion of `Shape`, for storing different kinds of shapes together.
+///
+/// Every `Shape` implements it, with the iterators boxed:
+///
+/// ```
+/// use glam::IVec2;
+/// use glam_px_shapes::DynShape;
+/// use glam_px_shapes::ivec2::{Circle, Rectangle};
+///
+/// let shapes: Vec<Box<dyn DynShape<IVec2>>> = vec![
+///     Box::new(Circle::new(IVec2::new(4, 4), 3)),
+///     Box::new(Rectangle::new(IVec2::new(0, 0), IVec2::new(2, 2))),
+/// ];
+/// let pixels: usize = shapes.iter().map(|shape| shape.fill_iter().count()).sum();
+/// ```
+pub trait DynShape<V> {
+    fn position(&self) -> V;
+    fn center(&self) -> V;
+    fn contains(&self, coord: V) -> bool;
+    fn bounding_box(&self) -> ivec2::Rectangle;
+    fn fill_iter(&self) -> Box<dyn Iterator<Item = IVec2> + '_>;
+    fn outline_iter(&self) -> Box<dyn Iterator<Item = IVec2> + '_>;
+    fn fill_iter_clipped(&self, clip: ivec2::Rectangle) -> Box<dyn Iterator<Item = IVec2> + '_>;
+    fn outline_iter_clipped(&self, clip: ivec2::Rectangle) -> Box<dyn Iterator<Item = IVec2> + '_>;
+}
+
+impl<S: Shape> DynShape<S::Vector> for S {
+    fn position(&self) -> S::Vector {
+        Shape::position(self)
+    }
+
+    fn center(&self) -> S::Vector {
+        Shape::center(self)
+    }
+
+    fn contains(&self, coord: S::Vector) -> bool {
+        Shape::contains(self, coord)
+    }
+
+    fn bounding_box(&self) -> ivec2::Rectangle {
+        Shape::bounding_box(self)
+    }
+
+    fn fill_iter(&self) -> Box<dyn Iterator<Item = IVec2> + '_> {
+        Box::new(Shape::fill_iter(self))
+    }
+
+    fn outline_iter(&self) -> Box<dyn Iterator<Item = IVec2> + '_> {
+        Box::new(Shape::outline_iter(self))
+    }
+
+    fn fill_iter_clipped(&self, clip: ivec2::Rectangle) -> Box<dyn Iterator<Item = IVec2> + '_> {
+        Box::new(Shape::fill_iter_clipped(self, clip))
+    }
+
+    fn outline_iter_clipped(&self, clip: ivec2::Rectangle) -> Box<dyn Iterator<Item = IVec2> + '_> {
+        Box::new(Shape::outline_iter_clipped(self, clip))
+    }
 }
 
+/// Shapes that can measure the distance from a point to their edge.
+///
+/// Points are in pixel space, pixel `(x, y)` covers the area from `(x, y)` to `(x + 1, y + 1)`.
+pub trait SignedDistance {
+    /// Returns the distance from `point` to the edge of the shape, negative inside of it.
+    fn signed_distance(&self, point: Vec2) -> f32;
+
+    /// Returns the pixels that contain the inside of the shape.
+    fn distance_bounds(&self) -> ivec2::Rectangle;
+
+    /// Returns an anti-aliased iterator over the pixels of the filled shape, yielding each pixel
+    /// with its coverage.
+    fn aa_fill_iter(&self) -> SdfPixels<&Self> {
+        SdfPixels::new(self, None)
+    }
+
+    /// Returns an anti-aliased iterator over the pixels of the outline of the shape, centered on
+    /// its edge, yielding each pixel with its coverage.
+    fn aa_outline_iter(&self, width: f32) -> SdfPixels<&Self> {
+        SdfPixels::new(self, Some(width))
+    }
+}
+
+impl<S: SignedDistance + ?Sized> SignedDistance for &S {
+    fn signed_distance(&self, point: Vec2) -> f32 {
+        (**self).signed_distance(point)
+    }
+
+    fn distance_bounds(&self) -> ivec2::Rectangle {
+        (**self).distance_bounds()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glam::UVec2;
+
+    use super::*;
+    use crate::iters::polygon_iter::FillRule;
+
+    /// Checks that the pixels of each shape lie inside its bounding box and that clipping keeps
+    /// exactly the pixels inside the clip rectangle.
+    fn check_dyn_shapes<V>(shapes: &[Box<dyn DynShape<V>>]) {
+        let clip = ivec2::Rectangle::new(IVec2::new(0, 0), IVec2::new(4, 4));
+
+        for shape in shapes {
+            let bounding_box = shape.bounding_box();
+            let inside = |p: &IVec2| bounding_box.columns().contains(&p.x) && bounding_box.rows().contains(&p.y);
+            assert!(shape.fill_iter().count() > 0);
+            assert!(shape.fill_iter().all(|p| inside(&p)), "{:?}", bounding_box);
+            assert!(shape.outline_iter().all(|p| inside(&p)), "{:?}", bounding_box);
+            assert!(shape.outline_iter().count() <= shape.fill_iter().count());
+
+            let in_clip = |p: &IVec2| clip.columns().contains(&p.x) && clip.rows().contains(&p.y);
+            assert!(shape.fill_iter().filter(in_clip).eq(shape.fill_iter_clipped(clip)));
+            assert!(shape.outline_iter().filter(in_clip).eq(shape.outline_iter_clipped(clip)));
+        }
+    }
+
+    #[test]
+    fn test_dyn_shapes() {
+        let shapes: Vec<Box<dyn DynShape<IVec2>>> = vec![
+            Box::new(ivec2::Circle::new(IVec2::new(4, 4), 3)),
+            Box::new(ivec2::Rectangle::new(IVec2::new(-2, 1), IVec2::new(5, 3))),
+            Box::new(ivec2::Triangle::new(IVec2::new(0, 0), IVec2::new(9, 2), IVec2::new(3, 8))),
+            Box::new(ivec2::Ellipse::new(IVec2::new(10, 10), IVec2::new(6, 3))),
+            Box::new(ivec2::Circle::new(IVec2::new(1, 0), 3)),
+            Box::new(ivec2::Polygon::new(
+                vec![IVec2::new(-3, -2), IVec2::new(6, 1), IVec2::new(2, 7)],
+                FillRule::NonZero,
+            )),
+            Box::new(ivec2::Capsule::new(ivec2::LineSegment::new(IVec2::new(-1, 2), IVec2::new(5, 0)), 2)),
+            Box::new(ivec2::RoundedRectangle::new(
+                ivec2::Rectangle::new(IVec2::new(-3, -3), IVec2::new(3, 5)),
+                2,
+            )),
+            Box::new(ivec2::Sector::new(ivec2::Circle::new(IVec2::new(0, 0), 4), 0.5, 2.5)),
+            Box::new(ivec2::LineSegment::new(IVec2::new(-2, 5), IVec2::new(6, -1))),
+        ];
+        check_dyn_shapes(&shapes);
+    }
+
+    #[test]
+    fn test_dyn_shapes_uvec2() {
+        // all of these reach the origin, so their bounds in their own coordinates would underflow
+        let shapes: Vec<Box<dyn DynShape<UVec2>>> = vec![
+            Box::new(uvec2::Circle::new(UVec2::new(1, 2), 3)),
+            Box::new(uvec2::Rectangle::new(UVec2::new(0, 1), UVec2::new(5, 3))),
+            Box::new(uvec2::Triangle::new(UVec2::new(0, 0), UVec2::new(9, 2), UVec2::new(3, 8))),
+            Box::new(uvec2::Ellipse::new(UVec2::new(2, 1), UVec2::new(4, 3))),
+            Box::new(uvec2::Polygon::new(
+                vec![UVec2::new(0, 0), UVec2::new(6, 1), UVec2::new(2, 7)],
+                FillRule::NonZero,
+            )),
+            Box::new(uvec2::Capsule::new(uvec2::LineSegment::new(UVec2::new(1, 1), UVec2::new(5, 1)), 2)),
+            Box::new(uvec2::RoundedRectangle::new(
+                uvec2::Rectangle::new(UVec2::new(0, 0), UVec2::new(6, 5)),
+                2,
+            )),
+            Box::new(uvec2::Sector::new(uvec2::Circle::new(UVec2::new(1, 1), 4), 0.5, 2.5)),
+            Box::new(uvec2::LineSegment::new(UVec2::new(0, 5), UVec2::new(6, 0))),
+        ];
+        check_dyn_shapes(&shapes);
+    }
+
+    #[test]
+    fn test_dyn_shapes_vec2() {
+        let shapes: Vec<Box<dyn DynShape<Vec2>>> = vec![
+            Box::new(vec2::Circle::new(Vec2::new(0.5, -1.5), 3.0)),
+            Box::new(vec2::Rectangle::new(Vec2::new(-1.5, 0.5), Vec2::new(3.5, 4.5))),
+            Box::new(vec2::Triangle::new(Vec2::new(-0.7, 0.2), Vec2::new(8.6, 2.4), Vec2::new(3.1, 7.9))),
+            Box::new(vec2::Ellipse::new(Vec2::new(1.3, 2.8), Vec2::new(4.5, 2.5))),
+            Box::new(vec2::Polygon::new(
+                vec![Vec2::new(-2.6, -1.4), Vec2::new(6.2, 0.7), Vec2::new(1.8, 6.6)],
+                FillRule::EvenOdd,
+            )),
+            Box::new(vec2::Capsule::new(vec2::LineSegment::new(Vec2::new(-0.5, 1.5), Vec2::new(4.5, 0.5)), 1.5)),
+            Box::new(vec2::RoundedRectangle::new(
+                vec2::Rectangle::new(Vec2::new(-2.5, -0.5), Vec2::new(4.5, 5.5)),
+                2.0,
+            )),
+            Box::new(vec2::Sector::new(vec2::Circle::new(Vec2::new(0.5, 0.5), 4.0), 0.5, 2.5)),
+            Box::new(vec2::LineSegment::new(Vec2::new(-2.3, 0.6), Vec2::new(5.7, 3.2))),
+        ];
+        check_dyn_shapes(&shapes);
+    }
+}
@@ -0,0 +1,111 @@
+use glam::UVec2;
+use crate::iters::arc_iter::{AngleRange, ArcPixels, ArcRingPixels};
+use super::circle::Circle;
+
+/// The part of the outline of a circle between two angles.
+///
+/// Angles are in radians, `0.0` points along the positive x axis and angles increase clockwise
+/// on screen. The arc runs clockwise from `start_angle` to `end_angle`, it covers the whole
+/// outline when they are a full turn or more apart.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Arc {
+    pub circle: Circle,
+    pub start_angle: f32,
+    pub end_angle: f32,
+}
+
+impl Arc {
+    pub const fn new(circle: Circle, start_angle: f32, end_angle: f32) -> Self {
+        Self {
+            circle,
+            start_angle,
+            end_angle,
+        }
+    }
+
+    /// Returns `true` if the pixel at `coord` is part of the arc.
+    pub fn contains(&self, coord: UVec2) -> bool {
+        crate::iters::arc_iter::arc_contains(&self.circle_ivec2(), self.angle_range(), coord.as_ivec2())
+    }
+
+    /// Returns an iterator over the pixels of the arc, walked clockwise from the start angle.
+    ///
+    /// The pixels are the ones `Circle::perimeter_iter` yields between the two angles.
+    pub fn pixel_iter(&self) -> ArcPixels {
+        ArcPixels::new(&self.circle_ivec2(), self.angle_range())
+    }
+
+    /// Returns an iterator over the pixels of the ring between the circle and a circle with the
+    /// same center and `inner_radius`, between the two angles.
+    ///
+    /// Pixels are yielded in scanline order and line up with `Circle::ring_pixel_iter`.
+    pub fn ring_pixel_iter(&self, inner_radius: u32) -> ArcRingPixels {
+        ArcRingPixels::new(&self.circle_ivec2(), inner_radius as i32, self.angle_range())
+    }
+
+    fn angle_range(&self) -> AngleRange {
+        AngleRange::new(self.start_angle, self.end_angle)
+    }
+
+    fn circle_ivec2(&self) -> crate::ivec2::Circle {
+        self.circle.as_circle_ivec2()
+        }
+    
+    pub fn as_arc_ivec2(&self) -> crate::ivec2::Arc {
+        crate::ivec2::Arc::new(self.circle.as_circle_ivec2(), self.start_angle, self.end_angle)
+    }
+    
+    
+    pub fn as_arc_vec2(&self) -> crate::vec2::Arc {
+        crate::vec2::Arc::new(self.circle.as_circle_vec2(), self.start_angle, self.end_angle)
+    }
+    
+    
+}
+
+impl crate::SignedDistance for Arc {
+    /// Returns the distance to the curve of the arc, which has no inside. Use `aa_outline_iter`
+    /// to draw the arc with a width.
+    fn signed_distance(&self, point: glam::Vec2) -> f32 {
+        crate::iters::arc_iter::arc_distance(self.circle.pos.as_vec2(), self.circle.radius as f32, self.angle_range(), point)
+    }
+
+    fn distance_bounds(&self) -> crate::ivec2::Rectangle {
+        crate::SignedDistance::distance_bounds(&self.circle)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_contains() {
+        let circle = Circle::new(UVec2::new(10, 10), 5);
+        let arc = Arc::new(circle, 0.0, std::f32::consts::PI);
+
+        assert!(arc.contains(UVec2::new(14, 10)));
+        assert!(arc.contains(UVec2::new(10, 14)));
+        assert!(!arc.contains(UVec2::new(10, 5)));
+        assert!(!arc.contains(UVec2::new(10, 10)));
+        assert!(arc.pixel_iter().all(|p| arc.as_arc_ivec2().contains(p)));
+    }
+
+    #[test]
+    fn test_signed_distance() {
+        use crate::SignedDistance;
+
+        let circle = Circle::new(UVec2::new(10, 10), 5);
+        let arc = Arc::new(circle, 0.0, std::f32::consts::PI);
+
+        assert_eq!(arc.signed_distance(glam::Vec2::new(10.0, 15.0)), 0.0);
+        assert_eq!(arc.signed_distance(glam::Vec2::new(10.0, 12.0)), 3.0);
+        // the closest point of the upper half is an end of the arc
+        assert_eq!(arc.signed_distance(glam::Vec2::new(15.0, 7.0)), 3.0);
+
+        let outline: Vec<(glam::IVec2, f32)> = arc.aa_outline_iter(1.0).collect();
+        assert!(outline.iter().all(|(p, _)| p.y >= 9));
+        assert!(outline.iter().any(|(p, _)| *p == glam::IVec2::new(9, 14)));
+    }
+}
@@ -1,7 +1,10 @@
 
 use glam::UVec2;
 use glam::IVec2;
-use crate::iters::circle_iter::CirclePoints;
+use crate::iters::circle_coverage_iter::CircleCoverage;
+use crate::iters::circle_iter::{CirclePoints, CircleSpans};
+use crate::iters::circle_perimeter_iter::{CirclePerimeter, Direction};
+use crate::iters::ring_iter::RingPixels;
 use super::rectangle::Rectangle;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
@@ -20,10 +23,12 @@ impl Circle {
         }
     }
 
-    /// Returns the bounding box of the circle, disregarding the limits.
-    pub fn bounding_box(&self) -> Rectangle {
+    /// Returns the bounds of the circle in its own coordinates, disregarding the limits.
+    ///
+    /// The bounds are clamped at zero for circles within `radius` of the origin.
+    pub fn bounds(&self) -> Rectangle {
         let r = UVec2::splat(self.radius);
-        let tl = self.pos - r;
+        let tl = self.pos.saturating_sub(r);
         let br = self.pos + r;
         Rectangle::new(tl, br)
     }
@@ -32,11 +37,12 @@ impl Circle {
     pub fn limited_bounding_box(&self) -> Rectangle {
         if let Some(limits) = self.limits {
             let r = UVec2::splat(self.radius);
-            let tl = (self.pos - r).max(limits.tl);
+            let tl = self.pos.saturating_sub(r);
+            let tl = tl.max(limits.tl);
             let br = (self.pos + r).min(limits.br);
             Rectangle::new(tl, br)
         } else {
-            self.bounding_box()
+            self.bounds()
         }
     }
 
@@ -71,22 +77,72 @@ impl Circle {
         CirclePoints::new(&self.as_circle_ivec2(), outline)
         
     }
+
+    /// Returns an iterator over the pixels of the shape that lie inside `clip`, the same pixels
+    /// `pixel_iter` yields inside of it.
+    pub fn pixel_iter_clipped(&self, outline: bool, clip: crate::ivec2::Rectangle) -> CirclePoints {
+        let mut circle = self.as_circle_ivec2();
+        circle.limits = Some(crate::iters::common::clip_limits(circle.limits, clip));
+        CirclePoints::new(&circle, outline)
+    }
+
+    /// Returns an anti-aliased iterator over the pixels of the filled circle, yielding each pixel
+    /// with its coverage.
+    ///
+    /// Unlike `pixel_iter`, the center and radius are not rounded to whole pixels.
+    pub fn aa_pixel_iter(&self) -> CircleCoverage {
+        CircleCoverage::new(self.pos.as_vec2(), self.radius as f32, self.limits.map(|limits| limits.as_rectangle_ivec2()))
+        }
+
+    /// Returns an iterator over the pixels of the ring between this circle and a circle with the
+    /// same center and `inner_radius`.
+    ///
+    /// The ring has no gaps and lines up exactly with filled circles, an inner radius one less
+    /// than the radius gives a 1px outline.
+    pub fn ring_pixel_iter(&self, inner_radius: u32) -> RingPixels {
+        RingPixels::new(&self.as_circle_ivec2(), inner_radius as i32)
+        }
+
+    /// Returns an iterator over the perimeter pixels of the circle in angular order.
+    ///
+    /// The walk begins at the first pixel at `start_angle` in radians, measured clockwise on
+    /// screen from the positive x axis, and goes around once in `direction`.
+    pub fn perimeter_iter(&self, start_angle: f32, direction: Direction) -> CirclePerimeter {
+        CirclePerimeter::new(&self.as_circle_ivec2(), start_angle, direction)
+        }
+
+    /// Returns an iterator over the rows of the filled circle, yielding each row with its range
+    /// of columns.
+    pub fn span_iter(&self) -> CircleSpans {
+        CircleSpans::new(&self.as_circle_ivec2())
+        }
     
     pub fn as_circle_ivec2(&self) -> crate::ivec2::Circle {
-        crate::ivec2::Circle::new(self.pos.as_ivec2(), self.radius as i32)
+        crate::ivec2::Circle {
+            pos: self.pos.as_ivec2(),
+            radius: self.radius as i32,
+            limits: self.limits.map(|limits| limits.as_rectangle_ivec2()),
+        }
     }
     
 
     
     pub fn as_circle_vec2(&self) -> crate::vec2::Circle {
-        crate::vec2::Circle::new(self.pos.as_vec2(), self.radius as f32)
+        crate::vec2::Circle {
+            pos: self.pos.as_vec2(),
+            radius: self.radius as f32,
+            limits: self.limits.map(|limits| limits.as_rectangle_vec2()),
+        }
     }
     
 
     
 }
 
-impl crate::Shape<UVec2> for Circle {
+impl crate::Shape for Circle {
+    type Vector = UVec2;
+    type Scalar = u32;
+
     fn position(&self) -> UVec2 {
         self.pos
     }
@@ -103,8 +159,25 @@ impl crate::Shape<UVec2> for Circle {
         distance < self.threshold()
     }
 
-    fn pixel_iter(&self, outline: bool) -> impl Iterator<Item = IVec2> {
-        self.pixel_iter(outline)
+    /// Returns the pixels of the circle, disregarding the limits.
+    fn bounding_box(&self) -> crate::ivec2::Rectangle {
+        self.as_circle_ivec2().bounds()
+        }
+
+    fn fill_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(false)
+    }
+
+    fn outline_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(true)
+    }
+
+    fn fill_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(false, clip)
+    }
+
+    fn outline_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(true, clip)
     }
 }
 
@@ -116,3 +189,15 @@ pub(crate) fn diameter_to_threshold(diameter: u32) -> u32 {
         }
 }
 
+
+impl crate::SignedDistance for Circle {
+    fn signed_distance(&self, point: glam::Vec2) -> f32 {
+        point.distance(self.pos.as_vec2()) - self.radius as f32
+    }
+
+    fn distance_bounds(&self) -> crate::ivec2::Rectangle {
+        let pos = self.pos.as_vec2();
+        let radius = glam::Vec2::splat(self.radius as f32);
+        crate::ivec2::Rectangle::new((pos - radius).floor().as_ivec2(), (pos + radius).ceil().as_ivec2())
+    }
+}
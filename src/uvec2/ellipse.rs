@@ -0,0 +1,192 @@
+use glam::UVec2;
+use glam::IVec2;
+use crate::iters::ellipse_iter::{EllipsePoints, EllipseSpans};
+use super::rectangle::Rectangle;
+
+/// An axis aligned ellipse with separate x and y radii.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Ellipse {
+    pub(crate) pos: UVec2,
+    pub(crate) radius: UVec2,
+    pub(crate) limits: Option<Rectangle>,
+}
+
+impl Ellipse {
+    /// Creates a new ellipse, `radius` holds the x and y radii.
+    pub const fn new(pos: UVec2, radius: UVec2) -> Self {
+        Self {
+            pos,
+            radius,
+            limits: None,
+        }
+    }
+
+    pub const fn new_with_limits(pos: UVec2, radius: UVec2, limits: Rectangle) -> Self {
+        Self {
+            pos,
+            radius,
+            limits: Some(limits),
+        }
+    }
+
+    /// Returns the bounds of the ellipse in its own coordinates, disregarding the limits.
+    ///
+    /// The bounds are clamped at zero for ellipses within their radii of the origin.
+    pub fn bounds(&self) -> Rectangle {
+        let tl = self.pos.saturating_sub(self.radius);
+        let br = self.pos + self.radius;
+        Rectangle::new(tl, br)
+    }
+
+    /// Returns the bounding box of the ellipse, respecting limits.
+    pub fn limited_bounding_box(&self) -> Rectangle {
+        if let Some(limits) = self.limits {
+            let tl = self.pos.saturating_sub(self.radius);
+            let tl = tl.max(limits.tl);
+            let br = (self.pos + self.radius).min(limits.br);
+            Rectangle::new(tl, br)
+        } else {
+            self.bounds()
+        }
+    }
+
+    /// Return the center point of the ellipse scaled by a factor of 2
+    ///
+    /// Uses the same maths as `Circle::center_2x`, so an ellipse with equal radii covers
+    /// exactly the same pixels as the circle with that radius.
+    #[allow(dead_code)]
+    pub(crate) fn center_2x(&self) -> UVec2 {
+        let radius_2x = self.radius * 2 - UVec2::splat(1);
+        (self.pos - self.radius) * 2 + radius_2x
+    }
+    
+
+    /// Returns an iterator over the pixels of the ellipse.
+    pub fn pixel_iter(&self, outline: bool) -> EllipsePoints {
+        
+        EllipsePoints::new(&self.as_ellipse_ivec2(), outline)
+        
+    }
+
+    /// Returns an iterator over the pixels of the shape that lie inside `clip`, the same pixels
+    /// `pixel_iter` yields inside of it.
+    pub fn pixel_iter_clipped(&self, outline: bool, clip: crate::ivec2::Rectangle) -> EllipsePoints {
+        let mut ellipse = self.as_ellipse_ivec2();
+        ellipse.limits = Some(crate::iters::common::clip_limits(ellipse.limits, clip));
+        EllipsePoints::new(&ellipse, outline)
+    }
+
+    /// Returns an iterator over the rows of the filled ellipse, yielding each row with its range
+    /// of columns.
+    pub fn span_iter(&self) -> EllipseSpans {
+        EllipseSpans::new(&self.as_ellipse_ivec2())
+        }
+    
+    pub fn as_ellipse_ivec2(&self) -> crate::ivec2::Ellipse {
+        crate::ivec2::Ellipse {
+            pos: self.pos.as_ivec2(),
+            radius: self.radius.as_ivec2(),
+            limits: self.limits.map(|limits| limits.as_rectangle_ivec2()),
+        }
+    }
+    
+
+    
+    pub fn as_ellipse_vec2(&self) -> crate::vec2::Ellipse {
+        crate::vec2::Ellipse {
+            pos: self.pos.as_vec2(),
+            radius: self.radius.as_vec2(),
+            limits: self.limits.map(|limits| limits.as_rectangle_vec2()),
+        }
+    }
+    
+
+    
+}
+
+impl crate::Shape for Ellipse {
+    type Vector = UVec2;
+    type Scalar = u32;
+
+    fn position(&self) -> UVec2 {
+        self.pos
+    }
+
+    fn center(&self) -> UVec2 {
+        self.pos
+    }
+
+    fn contains(&self, coord: UVec2) -> bool {
+        crate::Shape::contains(&self.as_ellipse_ivec2(), coord.as_ivec2())
+        
+    }
+
+    /// Returns the pixels of the ellipse, disregarding the limits.
+    fn bounding_box(&self) -> crate::ivec2::Rectangle {
+        self.as_ellipse_ivec2().bounds()
+        }
+
+    fn fill_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(false)
+    }
+
+    fn outline_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(true)
+    }
+
+    fn fill_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(false, clip)
+    }
+
+    fn outline_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(true, clip)
+    }
+}
+
+impl crate::SignedDistance for Ellipse {
+    /// Returns an approximation of the distance, exact on the edge and along the axes.
+    fn signed_distance(&self, point: glam::Vec2) -> f32 {
+        let radius = self.radius.as_vec2();
+        if radius.min_element() <= 0.0 {
+            return f32::INFINITY;
+        }
+        let offset = point - self.pos.as_vec2();
+        let k0 = (offset / radius).length();
+        let k1 = (offset / (radius * radius)).length();
+        if k1 == 0.0 {
+            return -radius.min_element();
+        }
+        k0 * (k0 - 1.0) / k1
+    }
+
+    fn distance_bounds(&self) -> crate::ivec2::Rectangle {
+        let pos = self.pos.as_vec2();
+        let radius = self.radius.as_vec2();
+        crate::ivec2::Rectangle::new((pos - radius).floor().as_ivec2(), (pos + radius).ceil().as_ivec2())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Shape;
+
+    #[test]
+    fn test_contains() {
+        let ellipse = Ellipse::new(UVec2::new(10, 10), UVec2::new(6, 3));
+        assert!(ellipse.contains(UVec2::new(10, 10)));
+        assert!(ellipse.contains(UVec2::new(4, 9)));
+        assert!(ellipse.contains(UVec2::new(15, 10)));
+        assert!(!ellipse.contains(UVec2::new(16, 10)));
+        assert!(!ellipse.contains(UVec2::new(10, 13)));
+        assert!(!ellipse.contains(UVec2::new(4, 7)));
+    }
+
+    #[test]
+    fn test_limited_bounding_box_near_origin() {
+        let limits = Rectangle::new(UVec2::new(0, 1), UVec2::new(20, 20));
+        let ellipse = Ellipse::new_with_limits(UVec2::new(2, 3), UVec2::new(6, 4), limits);
+        assert_eq!(ellipse.limited_bounding_box(), Rectangle::new(UVec2::new(0, 1), UVec2::new(8, 7)));
+    }
+}
@@ -1,4 +1,8 @@
 use crate::ivec2::{LineIter, LineDrawAlgo};
+use crate::iters::thick_line_iter::{LineCap, ThickLineIter};
+use crate::iters::wu_line_iter::WuLineIter;
+use crate::geometry::SegmentIntersection;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
 pub struct LineSegment {
     pub start: glam::UVec2,
@@ -25,12 +29,84 @@ impl LineSegment {
         
     }
 
+    /// Returns an iterator over the pixels of the line that lie inside `clip`.
+    ///
+    /// The line is clipped before it is walked, so pixels outside of `clip` are never visited.
+    pub fn pixel_iter_clipped(&self, draw_mode: LineDrawAlgo, clip: crate::ivec2::Rectangle) -> LineIter {
+        LineIter::new_from_segment_clipped(self.as_ivec2(), draw_mode, &clip)
+    }
     
+    /// Returns an anti-aliased iterator over the pixels of the line, yielding each pixel with
+    /// its coverage.
+    pub fn aa_pixel_iter(&self) -> WuLineIter {
+        WuLineIter::new(self.start.as_vec2(), self.end.as_vec2())
+        
+    }
+
+    /// Returns an iterator over the pixels of the line stroked with `width`, each covered pixel
+    /// is yielded exactly once.
+    pub fn thick_pixel_iter(&self, width: f32, cap: LineCap) -> ThickLineIter {
+        ThickLineIter::new(self.start.as_vec2(), self.end.as_vec2(), width, cap)
+        
+    }
 
     pub fn length (&self) -> u32 {
         self.start.as_vec2().distance_squared(self.end.as_vec2()).sqrt() as u32
         
     }
+
+    /// Returns the point halfway between the start and the end.
+    pub fn midpoint(&self) -> glam::Vec2 {
+        let (start, end) = self.points_vec2();
+        (start + end) / 2.0
+    }
+
+    /// Returns the unit vector pointing from the start to the end, zero if both are the same.
+    pub fn direction(&self) -> glam::Vec2 {
+        let (start, end) = self.points_vec2();
+        (end - start).normalize_or_zero()
+    }
+
+    /// Returns the point of the segment that is closest to `point`.
+    pub fn closest_point(&self, point: glam::Vec2) -> glam::Vec2 {
+        let (start, end) = self.points_vec2();
+        crate::geometry::closest_point(start, end, point)
+    }
+
+    /// Returns the distance from `point` to the segment.
+    ///
+    /// Unlike `SignedDistance`, the distance is measured in the coordinates of the end points
+    /// and not in pixel space.
+    pub fn distance(&self, point: glam::Vec2) -> f32 {
+        self.closest_point(point).distance(point)
+    }
+
+    /// Returns where the segment meets `other`, `None` if they don't touch.
+    ///
+    /// Collinear segments that share more than a single point return the shared part as
+    /// `SegmentIntersection::Overlap`.
+    pub fn intersection(&self, other: &Self) -> Option<SegmentIntersection> {
+        let (start, end) = self.points_vec2();
+        let (other_start, other_end) = other.points_vec2();
+        crate::geometry::intersection(start, end, other_start, other_end)
+    }
+
+    /// Splits the segment at `t` in `0.0..=1.0` into the part before and the part after the
+    /// point `start + (end - start) * t`.
+    pub fn split_at(&self, t: f32) -> (crate::vec2::LineSegment, crate::vec2::LineSegment) {
+        let (start, end) = self.points_vec2();
+        let point = start.lerp(end, t);
+        (crate::vec2::LineSegment::new(start, point), crate::vec2::LineSegment::new(point, end))
+    }
+
+    fn points_vec2(&self) -> (glam::Vec2, glam::Vec2) {
+        (self.start.as_vec2(), self.end.as_vec2())
+        }
+
+    /// Returns the segment between the pixels of the end points, which are pixel centers.
+    fn pixel_segment(&self) -> crate::ivec2::LineSegment {
+        self.as_ivec2()
+        }
     
     pub fn as_vec2(&self) -> crate::vec2::LineSegment {
         crate::vec2::LineSegment {
@@ -48,3 +124,126 @@ impl LineSegment {
     }
     }
 
+impl crate::SignedDistance for LineSegment {
+    /// Returns the distance to the line, which has no inside. The end points are pixel centers,
+    /// use `aa_outline_iter` to draw the line with a width.
+    fn signed_distance(&self, point: glam::Vec2) -> f32 {
+        let (start, end) = (self.start.as_vec2(), self.end.as_vec2());
+        let half = glam::Vec2::splat(0.5);
+        crate::iters::sdf_iter::segment_distance(start + half, end + half, point)
+    }
+
+    fn distance_bounds(&self) -> crate::ivec2::Rectangle {
+        let (start, end) = (self.start.as_vec2(), self.end.as_vec2());
+        let half = glam::Vec2::splat(0.5);
+        crate::ivec2::Rectangle::new(
+            (start.min(end) + half).floor().as_ivec2(),
+            (start.max(end) + half).ceil().as_ivec2(),
+        )
+    }
+}
+
+impl crate::Shape for LineSegment {
+    type Vector = glam::UVec2;
+    type Scalar = u32;
+
+    fn position(&self) -> glam::UVec2 {
+        self.start
+    }
+
+    /// Returns the midpoint of the segment.
+    fn center(&self) -> glam::UVec2 {
+        (self.start + self.end) / 2
+    }
+
+    /// Returns `true` if the pixel at `coord` is one of the pixels of `fill_iter`.
+    fn contains(&self, coord: glam::UVec2) -> bool {
+        let pixel = coord.as_ivec2();
+        let clip = crate::ivec2::Rectangle::new(pixel, pixel + glam::IVec2::ONE);
+        crate::Shape::fill_iter_clipped(self, clip).next().is_some()
+    }
+
+    fn bounding_box(&self) -> crate::ivec2::Rectangle {
+        let segment = self.pixel_segment();
+        crate::ivec2::Rectangle::new(segment.start.min(segment.end), segment.start.max(segment.end) + glam::IVec2::ONE)
+    }
+
+    /// Returns the pixels of the line drawn with Bresenham's algorithm.
+    fn fill_iter(&self) -> impl Iterator<Item = glam::IVec2> {
+        crate::ivec2::LineIter::new_from_segment(self.pixel_segment(), crate::ivec2::LineDrawAlgo::Bresenham)
+    }
+
+    /// A line has no inside, its outline are the same pixels as `fill_iter`.
+    fn outline_iter(&self) -> impl Iterator<Item = glam::IVec2> {
+        crate::Shape::fill_iter(self)
+    }
+
+    fn fill_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = glam::IVec2> {
+        crate::ivec2::LineIter::new_from_segment_clipped(self.pixel_segment(), crate::ivec2::LineDrawAlgo::Bresenham, &clip)
+    }
+
+    fn outline_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = glam::IVec2> {
+        crate::Shape::fill_iter_clipped(self, clip)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Shape;
+    use glam::UVec2;
+    use glam::Vec2;
+
+    #[test]
+    fn test_queries() {
+        let segment = LineSegment::new(UVec2::new(2, 2), UVec2::new(10, 2));
+
+        assert_eq!(segment.midpoint(), Vec2::new(6.0, 2.0));
+        assert_eq!(segment.direction(), Vec2::X);
+        assert_eq!(segment.closest_point(Vec2::new(4.0, 7.0)), Vec2::new(4.0, 2.0));
+        assert_eq!(segment.distance(Vec2::new(13.0, 6.0)), 5.0);
+
+        let (before, after) = segment.split_at(0.25);
+        assert_eq!((before.start, before.end), (Vec2::new(2.0, 2.0), Vec2::new(4.0, 2.0)));
+        assert_eq!((after.start, after.end), (Vec2::new(4.0, 2.0), Vec2::new(10.0, 2.0)));
+
+        let crossing = LineSegment::new(UVec2::new(5, 0), UVec2::new(5, 4));
+        assert_eq!(segment.intersection(&crossing), Some(SegmentIntersection::Point(Vec2::new(5.0, 2.0))));
+        let overlapping = LineSegment::new(UVec2::new(8, 2), UVec2::new(12, 2));
+        assert_eq!(
+            segment.intersection(&overlapping),
+            Some(SegmentIntersection::Overlap(crate::vec2::LineSegment::new(Vec2::new(8.0, 2.0), Vec2::new(10.0, 2.0))))
+        );
+    }
+
+    #[test]
+    fn test_shape() {
+        let segment = LineSegment::new(UVec2::new(1, 1), UVec2::new(9, 4));
+        let pixels: Vec<glam::IVec2> = segment.fill_iter().collect();
+
+        assert_eq!(pixels.first(), Some(&glam::IVec2::new(1, 1)));
+        assert_eq!(pixels.last(), Some(&glam::IVec2::new(9, 4)));
+        assert!(segment.outline_iter().eq(pixels.iter().copied()));
+        assert!(pixels.iter().all(|p| segment.contains(p.as_uvec2())));
+        assert!(!segment.contains(UVec2::new(9, 1)));
+
+        let bounding_box = Shape::bounding_box(&segment);
+        assert_eq!(bounding_box, crate::ivec2::Rectangle::new(glam::IVec2::new(1, 1), glam::IVec2::new(10, 5)));
+        assert_eq!(segment.center(), UVec2::new(5, 2));
+    }
+
+    #[test]
+    fn test_pixel_iter_clipped() {
+        let segment = LineSegment::new(UVec2::new(1, 1), UVec2::new(9, 4));
+        let clip = crate::ivec2::Rectangle::new(glam::IVec2::new(3, 0), glam::IVec2::new(7, 3));
+        let algo = LineDrawAlgo::Bresenham;
+        let inside = |p: &glam::IVec2| {
+            let pixel = *p;
+            clip.columns().contains(&pixel.x) && clip.rows().contains(&pixel.y)
+        };
+
+        let expected: Vec<_> = segment.pixel_iter(algo).filter(inside).collect();
+        assert!(!expected.is_empty());
+        assert_eq!(segment.pixel_iter_clipped(algo, clip).collect::<Vec<_>>(), expected);
+    }
+}
@@ -0,0 +1,156 @@
+use glam::UVec2;
+use glam::IVec2;
+use glam::Vec2;
+use crate::ivec2::LineDrawAlgo;
+use crate::iters::polyline_iter::{LineJoin, PolylinePixels, PolylineStrokePixels};
+use crate::iters::thick_line_iter::LineCap;
+
+/// A chain of line segments connecting a list of vertices.
+///
+/// A closed polyline also connects the last vertex back to the first one. Unlike drawing each
+/// segment on its own, the pixels shared by neighbouring segments are only yielded once.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Polyline {
+    pub vertices: Vec<UVec2>,
+    pub closed: bool,
+}
+
+impl Polyline {
+    pub fn new(vertices: Vec<UVec2>, closed: bool) -> Self {
+        Self { vertices, closed }
+    }
+
+    /// Returns the segments of the polyline, including the closing one if the polyline is closed.
+    ///
+    /// Like the pixel iterators, repeated vertices are skipped, so there are no zero length
+    /// segments.
+    pub fn segments(&self) -> Vec<super::LineSegment> {
+        let mut segments: Vec<super::LineSegment> = self
+            .vertices
+            .windows(2)
+            .filter(|pair| pair[0] != pair[1])
+            .map(|pair| super::LineSegment::new(pair[0], pair[1]))
+            .collect();
+        if self.closed && self.vertices.len() > 2 {
+            let (first, last) = (self.vertices[0], self.vertices[self.vertices.len() - 1]);
+            if first != last {
+                segments.push(super::LineSegment::new(last, first));
+            }
+        }
+        segments
+    }
+
+    /// Returns an iterator over the pixels of the 1px wide polyline.
+    pub fn pixel_iter(&self, draw_mode: LineDrawAlgo) -> PolylinePixels {
+        PolylinePixels::new(&self.vertices_ivec2(), self.closed, draw_mode, None)
+    }
+
+    /// Returns an iterator over the pixels of the 1px wide polyline that lie inside `clip`.
+    ///
+    /// Each segment is clipped before it is walked, so pixels outside of `clip` are never visited.
+    pub fn pixel_iter_clipped(&self, draw_mode: LineDrawAlgo, clip: crate::ivec2::Rectangle) -> PolylinePixels {
+        PolylinePixels::new(&self.vertices_ivec2(), self.closed, draw_mode, Some(clip))
+    }
+
+    fn vertices_ivec2(&self) -> Vec<IVec2> {
+        self.vertices.iter().map(|v| v.as_ivec2()).collect()
+        }
+
+    /// Returns an iterator over the pixels of the polyline stroked with `width`.
+    ///
+    /// Segments meet with the given `join`, the ends of an open polyline use `cap`. Each covered
+    /// pixel is yielded exactly once.
+    pub fn stroke_pixel_iter(&self, width: f32, join: LineJoin, cap: LineCap) -> PolylineStrokePixels {
+        PolylineStrokePixels::new(&self.vertices_vec2(), self.closed, width, join, cap)
+    }
+
+    fn vertices_vec2(&self) -> Vec<Vec2> {
+        self.vertices.iter().map(|v| v.as_vec2()).collect()
+        }
+    
+    pub fn as_polyline_ivec2(&self) -> crate::ivec2::Polyline {
+        crate::ivec2::Polyline::new(self.vertices.iter().map(|v| v.as_ivec2()).collect(), self.closed)
+    }
+    
+    
+    pub fn as_polyline_vec2(&self) -> crate::vec2::Polyline {
+        crate::vec2::Polyline::new(self.vertices_vec2(), self.closed)
+    }
+    
+    
+}
+
+impl crate::SignedDistance for Polyline {
+    /// Returns the distance to the nearest segment, the polyline has no inside even if it is
+    /// closed. Like `LineSegment`, the vertices are pixel centers. A polyline without segments is
+    /// infinitely far from every point.
+    fn signed_distance(&self, point: Vec2) -> f32 {
+        self.segments()
+            .iter()
+            .map(|segment| crate::SignedDistance::signed_distance(segment, point))
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    fn distance_bounds(&self) -> crate::ivec2::Rectangle {
+        let vertices = self.vertices_vec2();
+        if vertices.is_empty() {
+            return crate::ivec2::Rectangle::default();
+        }
+        let min = vertices.iter().copied().fold(Vec2::INFINITY, Vec2::min);
+        let max = vertices.iter().copied().fold(Vec2::NEG_INFINITY, Vec2::max);
+        let half = Vec2::splat(0.5);
+        crate::ivec2::Rectangle::new((min + half).floor().as_ivec2(), (max + half).ceil().as_ivec2())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pixel_iter() {
+        let polyline = Polyline::new(vec![
+            UVec2::new(0, 0),
+            UVec2::new(4, 0),
+            UVec2::new(4, 4),
+        ], false);
+        assert_eq!(polyline.segments().len(), 2);
+        assert_eq!(polyline.pixel_iter(LineDrawAlgo::Bresenham).count(), 9);
+        assert_eq!(polyline.stroke_pixel_iter(1.0, LineJoin::Miter, LineCap::Butt).count(), 9);
+    }
+
+    #[test]
+    fn test_segments_skip_repeated_vertices() {
+        let polyline = Polyline::new(vec![
+            UVec2::new(0, 0),
+            UVec2::new(4, 0),
+            UVec2::new(4, 0),
+            UVec2::new(4, 4),
+            UVec2::new(0, 0),
+        ], true);
+        let segments = polyline.segments();
+
+        assert_eq!(segments.len(), 3);
+        assert!(segments.iter().all(|segment| segment.start != segment.end));
+        assert_eq!(segments[1], crate::uvec2::LineSegment::new(UVec2::new(4, 0), UVec2::new(4, 4)));
+    }
+
+    #[test]
+    fn test_signed_distance() {
+        use crate::SignedDistance;
+
+        let polyline = Polyline::new(vec![
+            UVec2::new(0, 0),
+            UVec2::new(4, 0),
+            UVec2::new(4, 4),
+        ], false);
+
+        assert_eq!(polyline.signed_distance(Vec2::new(2.5, 0.5)), 0.0);
+        // closer to the second segment than to the first one
+        assert_eq!(polyline.signed_distance(Vec2::new(2.5, 3.5)), 2.0);
+        assert_eq!(polyline.signed_distance(Vec2::new(7.5, 8.5)), 5.0);
+        assert_eq!(polyline.distance_bounds(), crate::ivec2::Rectangle::new(IVec2::new(0, 0), IVec2::new(5, 5)));
+        assert!(polyline.aa_outline_iter(1.0).any(|(p, _)| p == IVec2::new(4, 2)));
+    }
+}
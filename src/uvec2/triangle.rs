@@ -0,0 +1,139 @@
+use glam::UVec2;
+use glam::IVec2;
+use glam::Vec2;
+use crate::iters::triangle_iter::{TrianglePixels, TriangleSpans};
+
+/// A triangle, filled following the top-left rule.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Triangle {
+    pub a: UVec2,
+    pub b: UVec2,
+    pub c: UVec2,
+}
+
+impl Triangle {
+    pub const fn new(a: UVec2, b: UVec2, c: UVec2) -> Self {
+        Self { a, b, c }
+    }
+
+    pub fn vertices(&self) -> [UVec2; 3] {
+        [self.a, self.b, self.c]
+    }
+
+    /// Returns the pixel bounding box of the triangle.
+    pub fn bounding_box(&self) -> crate::ivec2::Rectangle {
+        let [a, b, c] = self.vertices_vec2();
+        crate::ivec2::Rectangle::new(
+            a.min(b).min(c).floor().as_ivec2(),
+            a.max(b).max(c).ceil().as_ivec2(),
+        )
+    }
+
+    /// Returns an iterator over the pixels of the triangle.
+    pub fn pixel_iter(&self, outline: bool) -> TrianglePixels {
+        TrianglePixels::new(self.vertices_vec2(), None, outline)
+    }
+
+    /// Returns an iterator over the pixels of the shape that lie inside `clip`, the same pixels
+    /// `pixel_iter` yields inside of it.
+    pub fn pixel_iter_clipped(&self, outline: bool, clip: crate::ivec2::Rectangle) -> TrianglePixels {
+        TrianglePixels::new(self.vertices_vec2(), Some(clip), outline)
+    }
+
+    /// Returns an iterator over the rows of the filled triangle, yielding each row with its range
+    /// of columns.
+    pub fn span_iter(&self) -> TriangleSpans {
+        TriangleSpans::new(self.vertices_vec2(), None)
+    }
+
+    fn vertices_vec2(&self) -> [Vec2; 3] {
+        self.vertices().map(|v| v.as_vec2())
+        }
+    
+    pub fn as_triangle_ivec2(&self) -> crate::ivec2::Triangle {
+        crate::ivec2::Triangle::new(self.a.as_ivec2(), self.b.as_ivec2(), self.c.as_ivec2())
+    }
+    
+    
+    pub fn as_triangle_vec2(&self) -> crate::vec2::Triangle {
+        crate::vec2::Triangle::new(self.a.as_vec2(), self.b.as_vec2(), self.c.as_vec2())
+    }
+    
+    
+}
+
+impl crate::Shape for Triangle {
+    type Vector = UVec2;
+    type Scalar = u32;
+
+    fn position(&self) -> UVec2 {
+        self.a.min(self.b).min(self.c)
+    }
+
+    /// Returns the centroid of the triangle.
+    fn center(&self) -> UVec2 {
+        (self.a + self.b + self.c) / 3
+    }
+
+    /// Returns `true` if the pixel at `coord` is filled, using the top-left rule.
+    fn contains(&self, coord: UVec2) -> bool {
+        crate::iters::triangle_iter::contains(self.vertices_vec2(), coord.as_ivec2())
+    }
+
+    fn bounding_box(&self) -> crate::ivec2::Rectangle {
+        Triangle::bounding_box(self)
+    }
+
+    fn fill_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(false)
+    }
+
+    fn outline_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(true)
+    }
+
+    fn fill_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(false, clip)
+    }
+
+    fn outline_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(true, clip)
+    }
+}
+
+impl crate::SignedDistance for Triangle {
+    fn signed_distance(&self, point: Vec2) -> f32 {
+        let vertices = self.vertices_vec2();
+        let inside = crate::iters::polygon_iter::contains(
+            &vertices,
+            crate::iters::polygon_iter::FillRule::NonZero,
+            point - Vec2::splat(0.5),
+        );
+        crate::iters::sdf_iter::outline_distance(&vertices, inside, point)
+    }
+
+    fn distance_bounds(&self) -> crate::ivec2::Rectangle {
+        self.bounding_box()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Shape;
+
+    #[test]
+    fn test_contains() {
+        let triangle = Triangle::new(
+            UVec2::new(0, 0),
+            UVec2::new(4, 0),
+            UVec2::new(0, 4),
+        );
+        assert!(triangle.contains(UVec2::new(0, 0)));
+        assert!(triangle.contains(UVec2::new(2, 0)));
+        assert!(!triangle.contains(UVec2::new(3, 0)));
+        assert!(!triangle.contains(UVec2::new(2, 2)));
+        assert_eq!(triangle.bounding_box(), crate::ivec2::Rectangle::new(IVec2::new(0, 0), IVec2::new(4, 4)));
+    }
+}
@@ -0,0 +1,207 @@
+use glam::Vec2;
+use glam::IVec2;
+use crate::iters::ellipse_iter::{EllipsePoints, EllipseSpans};
+use super::rectangle::Rectangle;
+
+/// An axis aligned ellipse with separate x and y radii.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Ellipse {
+    pub(crate) pos: Vec2,
+    pub(crate) radius: Vec2,
+    pub(crate) limits: Option<Rectangle>,
+}
+
+impl PartialEq for Ellipse {
+    fn eq(&self, other: &Ellipse) -> bool {
+        self.pos.abs_diff_eq(other.pos, f32::EPSILON)
+            && self.radius.abs_diff_eq(other.radius, f32::EPSILON)
+            && self.limits == other.limits
+    }
+}
+
+impl Eq for Ellipse {}
+
+impl Ellipse {
+    /// Creates a new ellipse, `radius` holds the x and y radii.
+    pub const fn new(pos: Vec2, radius: Vec2) -> Self {
+        Self {
+            pos,
+            radius,
+            limits: None,
+        }
+    }
+
+    pub const fn new_with_limits(pos: Vec2, radius: Vec2, limits: Rectangle) -> Self {
+        Self {
+            pos,
+            radius,
+            limits: Some(limits),
+        }
+    }
+
+    /// Returns the bounds of the ellipse in its own coordinates, disregarding the limits.
+    pub fn bounds(&self) -> Rectangle {
+        let tl = self.pos - self.radius;
+        let br = self.pos + self.radius;
+        Rectangle::new(tl, br)
+    }
+
+    /// Returns the bounding box of the ellipse, respecting limits.
+    pub fn limited_bounding_box(&self) -> Rectangle {
+        if let Some(limits) = self.limits {
+            let tl = self.pos - self.radius;
+            let tl = tl.max(limits.tl);
+            let br = (self.pos + self.radius).min(limits.br);
+            Rectangle::new(tl, br)
+        } else {
+            self.bounds()
+        }
+    }
+
+    /// Return the center point of the ellipse scaled by a factor of 2
+    ///
+    /// Uses the same maths as `Circle::center_2x`, so an ellipse with equal radii covers
+    /// exactly the same pixels as the circle with that radius.
+    #[allow(dead_code)]
+    pub(crate) fn center_2x(&self) -> Vec2 {
+        let radius_2x = self.radius * 2.0 - Vec2::splat(1.0);
+        (self.pos - self.radius) * 2.0 + radius_2x
+    }
+    
+    /// Returns the threshold for this ellipses radii.
+    ///
+    /// The squared x and y distances to the doubled center are weighted by the radius of the
+    /// other axis, which reduces to the `Circle` threshold when both radii are equal.
+    #[allow(dead_code)]
+    pub(crate) fn threshold(&self) -> f32 {
+        let x = super::circle::diameter_to_threshold(self.radius.x * 2.0) * self.radius.y.powf(2.0);
+        let y = super::circle::diameter_to_threshold(self.radius.y * 2.0) * self.radius.x.powf(2.0);
+        x.min(y)
+    }
+    
+
+    /// Returns an iterator over the pixels of the ellipse.
+    pub fn pixel_iter(&self, outline: bool) -> EllipsePoints {
+        
+        EllipsePoints::new(&self.as_ellipse_ivec2(), outline)
+        
+    }
+
+    /// Returns an iterator over the pixels of the shape that lie inside `clip`, the same pixels
+    /// `pixel_iter` yields inside of it.
+    pub fn pixel_iter_clipped(&self, outline: bool, clip: crate::ivec2::Rectangle) -> EllipsePoints {
+        let mut ellipse = self.as_ellipse_ivec2();
+        ellipse.limits = Some(crate::iters::common::clip_limits(ellipse.limits, clip));
+        EllipsePoints::new(&ellipse, outline)
+    }
+
+    /// Returns an iterator over the rows of the filled ellipse, yielding each row with its range
+    /// of columns.
+    pub fn span_iter(&self) -> EllipseSpans {
+        EllipseSpans::new(&self.as_ellipse_ivec2())
+        }
+    
+    pub fn as_ellipse_ivec2(&self) -> crate::ivec2::Ellipse {
+        crate::ivec2::Ellipse {
+            pos: self.pos.as_ivec2(),
+            radius: self.radius.as_ivec2(),
+            limits: self.limits.map(|limits| limits.as_rectangle_ivec2()),
+        }
+    }
+    
+
+    
+
+    
+    pub fn as_ellipse_uvec2(&self) -> crate::uvec2::Ellipse {
+        crate::uvec2::Ellipse {
+            pos: self.pos.as_uvec2(),
+            radius: self.radius.as_uvec2(),
+            limits: self.limits.map(|limits| limits.as_rectangle_uvec2()),
+        }
+    }
+    
+}
+
+impl crate::Shape for Ellipse {
+    type Vector = Vec2;
+    type Scalar = f32;
+
+    fn position(&self) -> Vec2 {
+        self.pos
+    }
+
+    fn center(&self) -> Vec2 {
+        self.pos
+    }
+
+    fn contains(&self, coord: Vec2) -> bool {
+        let delta = coord * 2.0 - self.center_2x();
+        let distance = delta.x.powf(2.0) * self.radius.y.powf(2.0) + delta.y.powf(2.0) * self.radius.x.powf(2.0);
+
+        distance < self.threshold()
+        
+    }
+
+    /// Returns the pixels of the ellipse, disregarding the limits.
+    fn bounding_box(&self) -> crate::ivec2::Rectangle {
+        self.as_ellipse_ivec2().bounds()
+        }
+
+    fn fill_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(false)
+    }
+
+    fn outline_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(true)
+    }
+
+    fn fill_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(false, clip)
+    }
+
+    fn outline_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(true, clip)
+    }
+}
+
+impl crate::SignedDistance for Ellipse {
+    /// Returns an approximation of the distance, exact on the edge and along the axes.
+    fn signed_distance(&self, point: glam::Vec2) -> f32 {
+        let radius = self.radius;
+        if radius.min_element() <= 0.0 {
+            return f32::INFINITY;
+        }
+        let offset = point - self.pos;
+        let k0 = (offset / radius).length();
+        let k1 = (offset / (radius * radius)).length();
+        if k1 == 0.0 {
+            return -radius.min_element();
+        }
+        k0 * (k0 - 1.0) / k1
+    }
+
+    fn distance_bounds(&self) -> crate::ivec2::Rectangle {
+        let pos = self.pos;
+        let radius = self.radius;
+        crate::ivec2::Rectangle::new((pos - radius).floor().as_ivec2(), (pos + radius).ceil().as_ivec2())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Shape;
+
+    #[test]
+    fn test_contains() {
+        let ellipse = Ellipse::new(Vec2::new(10.0, 10.0), Vec2::new(6.0, 3.0));
+        assert!(ellipse.contains(Vec2::new(10.0, 10.0)));
+        assert!(ellipse.contains(Vec2::new(4.0, 9.0)));
+        assert!(ellipse.contains(Vec2::new(15.0, 10.0)));
+        assert!(!ellipse.contains(Vec2::new(16.0, 10.0)));
+        assert!(!ellipse.contains(Vec2::new(10.0, 13.0)));
+        assert!(!ellipse.contains(Vec2::new(4.0, 7.0)));
+    }
+}
@@ -1,12 +1,14 @@
 use glam::Vec2;
 use strum::EnumDiscriminants;
 
-use crate::vec2::LineSegment;
+use crate::vec2::{LineSegment, Rectangle};
 
 
 #[derive(Debug, Copy, Clone)]
 pub struct LineIter {
     impl_iter: LineIterImpl,
+    /// Number of points left to yield.
+    remaining: u32,
 }
 
 
@@ -30,43 +32,141 @@ impl LineIter {
                 
                 LineIter {
                     impl_iter: LineIterImpl::DDA(DDAParams {
-                        // start: ls.start,
+                        start: ls.start,
                         end: ls.end,
-                        current: ls.start,
                         step,
                         steps,
                         current_step: 0,
+                        last_step: steps,
                     }),
+                    remaining: steps.max(0) as u32 + 1,
                 }
             },
         }
     }
 
+    /// Creates an iterator over the points of the line that lie inside `clip`.
+    ///
+    /// The line is clipped with Liang–Barsky before it is walked, the iterator starts at the
+    /// first step inside `clip` and stops after the last one. Points are inside if they lie in
+    /// the half open range `clip.tl..clip.br`, like the pixels of a rectangle.
+    pub fn new_from_segment_clipped(ls: LineSegment, iter_type: LineDrawAlgo, clip: &Rectangle) -> LineIter {
+        let mut iter = Self::new_from_segment(ls, iter_type);
+        iter.remaining = match &mut iter.impl_iter {
+            LineIterImpl::DDA(params) => params.clip(clip),
+        };
+        iter
+    }
+
 
 }
 impl Iterator for LineIter {
     type Item = Vec2;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
         self.impl_iter.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
 }
 
+/// Yields the points of the line in reverse order, from the end to the start.
+impl DoubleEndedIterator for LineIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        match &self.impl_iter {
+            LineIterImpl::DDA(params) => Some(params.point_at(params.current_step + self.remaining as i32)),
+        }
+    }
+}
+
+impl ExactSizeIterator for LineIter {}
+
 
 #[derive(Debug, Copy, Clone, Default)]
 pub struct DDAParams {
-    // start: Vec2,
+    start: Vec2,
     end: Vec2,
-    current: Vec2,
     step: Vec2,
     steps: i32,
     current_step: i32,
+    last_step: i32,
 }
 
+impl DDAParams {
+    /// Returns the point yielded at step `k`.
+    fn point_at(&self, k: i32) -> Vec2 {
+        if k == self.steps {
+            self.end
+        } else {
+            self.start + self.step * k as f32
+        }
+    }
+
+    /// Restricts the iterator to the steps inside `clip` and returns the number of them.
+    fn clip(&mut self, clip: &Rectangle) -> u32 {
+        let start = self.start;
+        let inside = |point: Vec2| point.cmpge(clip.tl).all() && point.cmplt(clip.br).all();
+
+        // Liang–Barsky: intersect the ranges of `t` for which each axis is inside the clip
+        let mut t_min = 0.0_f32;
+        let mut t_max = self.steps as f32;
+        for (p, s, min, max) in [
+            (start.x, self.step.x, clip.tl.x, clip.br.x),
+            (start.y, self.step.y, clip.tl.y, clip.br.y),
+        ] {
+            if s == 0.0 || s.is_nan() {
+                if p < min || p >= max {
+                    t_max = -1.0;
+                }
+                continue;
+            }
+            let (a, b) = ((min - p) / s, (max - p) / s);
+            t_min = t_min.max(a.min(b));
+            t_max = t_max.min(a.max(b));
+        }
+
+        if t_min > t_max {
+            self.current_step = self.steps + 1;
+            return 0;
+        }
 
+        // the steps around the solved range are checked directly, so rounding can't add or drop
+        // points at the edges of the clip
+        let mut first = (t_min.ceil() as i32 - 1).max(0);
+        let mut last = (t_max.floor() as i32 + 1).min(self.steps);
+        while first <= last && !inside(self.point_at(first)) {
+            first += 1;
+        }
+        while last >= first && !inside(self.point_at(last)) {
+            last -= 1;
+        }
 
+        if first > last {
+            self.current_step = self.steps + 1;
+            return 0;
+        }
+        self.current_step = first;
+        self.last_step = last;
+        (last - first + 1) as u32
+    }
+}
+
+
+
+#[allow(clippy::upper_case_acronyms)]
 #[derive(EnumDiscriminants, Debug, Copy, Clone)]
 #[strum_discriminants(vis(pub))]
+#[strum_discriminants(allow(clippy::upper_case_acronyms))]
 #[strum_discriminants(name(LineDrawAlgo))]
 pub(crate) enum LineIterImpl {
     DDA(DDAParams),
@@ -78,19 +178,11 @@ impl Iterator for LineIterImpl {
     fn next(&mut self) -> Option<Self::Item> {
         match self {
             LineIterImpl::DDA(params) => {
-                if params.current_step > params.steps {
+                if params.current_step > params.last_step {
                     return None;
                 }
 
-                let current = params.current;
-
-                if params.current_step == params.steps {
-                    params.current += params.step;
-                    params.current_step += 1;
-                    return Some(params.end);
-                }
-
-                params.current += params.step;
+                let current = params.point_at(params.current_step);
                 params.current_step += 1;
 
                 Some(current)
@@ -149,19 +241,70 @@ mod test {
             Vec2::new(2.0, 1.4),
             Vec2::new(3.0, 1.8),
             Vec2::new(4.0, 2.2),
-            Vec2::new(5.0, 2.6000001),
-            Vec2::new(6.0, 3.0000002),
-            Vec2::new(7.0, 3.4000003),
-            Vec2::new(8.0, 3.8000004),
-            Vec2::new(9.0, 4.2000003),
+            Vec2::new(5.0, 2.6),
+            Vec2::new(6.0, 3.0),
+            Vec2::new(7.0, 3.4),
+            Vec2::new(8.0, 3.8),
+            Vec2::new(9.0, 4.2),
             Vec2::new(10.0, 4.6000004),
-            Vec2::new(11.0, 5.0000005)
+            Vec2::new(11.0, 5.0)
         ];
 
         assert_eq!(iter.collect::<Vec<Vec2>>(), expected);
 
     }
 
+    #[test]
+    fn test_dda_clipped() {
+        let ls = LineSegment::new(Vec2::new(-4.0, 2.0), Vec2::new(12.0, 6.0));
+        let clip = Rectangle::new(Vec2::new(0.0, 0.0), Vec2::new(8.0, 8.0));
+        let expected: Vec<Vec2> = LineIter::new_from_segment(ls, LineDrawAlgo::DDA)
+            .filter(|p| p.cmpge(clip.tl).all() && p.cmplt(clip.br).all())
+            .collect();
+        let clipped: Vec<Vec2> = LineIter::new_from_segment_clipped(ls, LineDrawAlgo::DDA, &clip).collect();
+
+        assert_eq!(clipped, expected);
+        assert_eq!(clipped.first(), Some(&Vec2::new(0.0, 3.0)));
+        assert_eq!(clipped.last(), Some(&Vec2::new(7.0, 4.75)));
+
+        let outside = Rectangle::new(Vec2::new(20.0, 0.0), Vec2::new(30.0, 8.0));
+        assert_eq!(LineIter::new_from_segment_clipped(ls, LineDrawAlgo::DDA, &outside).count(), 0);
+    }
+
+    #[test]
+    fn test_dda_double_ended() {
+        let ls = LineSegment::new(Vec2::new(-4.0, 2.0), Vec2::new(12.0, 6.0));
+        let clip = Rectangle::new(Vec2::new(0.0, 0.0), Vec2::new(8.0, 8.0));
+        let iters = [
+            LineIter::new_from_segment(ls, LineDrawAlgo::DDA),
+            LineIter::new_from_segment_clipped(ls, LineDrawAlgo::DDA, &clip),
+            LineIter::new_from_segment(LineSegment::new(Vec2::new(1.5, 2.5), Vec2::new(1.5, 2.5)), LineDrawAlgo::DDA),
+        ];
+
+        for iter in iters {
+            let forward: Vec<Vec2> = iter.collect();
+            assert_eq!(iter.len(), forward.len());
+
+            let mut reversed: Vec<Vec2> = iter.rev().collect();
+            reversed.reverse();
+            assert_eq!(reversed, forward);
+
+            // both ends meet in the middle
+            let mut iter = iter;
+            let mut mixed = Vec::new();
+            let mut tail = Vec::new();
+            while let Some(p) = iter.next() {
+                mixed.push(p);
+                assert_eq!(iter.len(), forward.len() - mixed.len() - tail.len());
+                if let Some(p) = iter.next_back() {
+                    tail.push(p);
+                }
+            }
+            mixed.extend(tail.into_iter().rev());
+            assert_eq!(mixed, forward);
+        }
+    }
+
     #[test]
     fn test_dda_line_iter_impl_going_left() {
         let ls = LineSegment::new(Vec2::new(0.0, 0.0), Vec2::new(-5.0, 5.0));
@@ -1,5 +1,9 @@
 use crate::vec2::{LineIter, LineDrawAlgo};
-#[derive(Debug, Copy, Clone, Default)]
+use crate::iters::thick_line_iter::{LineCap, ThickLineIter};
+use crate::iters::wu_line_iter::WuLineIter;
+use crate::geometry::SegmentIntersection;
+
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 pub struct LineSegment {
     pub start: glam::Vec2,
     pub end: glam::Vec2,
@@ -23,12 +27,88 @@ impl LineSegment {
         
     }
 
+    /// Returns an iterator over the pixels of the line that lie inside `clip`.
+    ///
+    /// The line is clipped before it is walked, so pixels outside of `clip` are never visited.
+    /// A point lies inside `clip` if the pixel it rounds to does.
+    pub fn pixel_iter_clipped(&self, draw_mode: LineDrawAlgo, clip: crate::ivec2::Rectangle) -> LineIter {
+        let half = glam::Vec2::splat(0.5);
+        let clip = crate::vec2::Rectangle::new(clip.tl.as_vec2() - half, clip.br.as_vec2() - half);
+        LineIter::new_from_segment_clipped(*self, draw_mode, &clip)
+    }
     
+    /// Returns an anti-aliased iterator over the pixels of the line, yielding each pixel with
+    /// its coverage.
+    pub fn aa_pixel_iter(&self) -> WuLineIter {
+        WuLineIter::new(self.start, self.end)
+        
+    }
+
+    /// Returns an iterator over the pixels of the line stroked with `width`, each covered pixel
+    /// is yielded exactly once.
+    pub fn thick_pixel_iter(&self, width: f32, cap: LineCap) -> ThickLineIter {
+        ThickLineIter::new(self.start, self.end, width, cap)
+        
+    }
 
     pub fn length (&self) -> f32 {
         self.start.distance_squared(self.end).sqrt()
         
     }
+
+    /// Returns the point halfway between the start and the end.
+    pub fn midpoint(&self) -> glam::Vec2 {
+        let (start, end) = self.points_vec2();
+        (start + end) / 2.0
+    }
+
+    /// Returns the unit vector pointing from the start to the end, zero if both are the same.
+    pub fn direction(&self) -> glam::Vec2 {
+        let (start, end) = self.points_vec2();
+        (end - start).normalize_or_zero()
+    }
+
+    /// Returns the point of the segment that is closest to `point`.
+    pub fn closest_point(&self, point: glam::Vec2) -> glam::Vec2 {
+        let (start, end) = self.points_vec2();
+        crate::geometry::closest_point(start, end, point)
+    }
+
+    /// Returns the distance from `point` to the segment.
+    ///
+    /// Unlike `SignedDistance`, the distance is measured in the coordinates of the end points
+    /// and not in pixel space.
+    pub fn distance(&self, point: glam::Vec2) -> f32 {
+        self.closest_point(point).distance(point)
+    }
+
+    /// Returns where the segment meets `other`, `None` if they don't touch.
+    ///
+    /// Collinear segments that share more than a single point return the shared part as
+    /// `SegmentIntersection::Overlap`.
+    pub fn intersection(&self, other: &Self) -> Option<SegmentIntersection> {
+        let (start, end) = self.points_vec2();
+        let (other_start, other_end) = other.points_vec2();
+        crate::geometry::intersection(start, end, other_start, other_end)
+    }
+
+    /// Splits the segment at `t` in `0.0..=1.0` into the part before and the part after the
+    /// point `start + (end - start) * t`.
+    pub fn split_at(&self, t: f32) -> (crate::vec2::LineSegment, crate::vec2::LineSegment) {
+        let (start, end) = self.points_vec2();
+        let point = start.lerp(end, t);
+        (crate::vec2::LineSegment::new(start, point), crate::vec2::LineSegment::new(point, end))
+    }
+
+    fn points_vec2(&self) -> (glam::Vec2, glam::Vec2) {
+        (self.start, self.end)
+        }
+
+    /// Returns the segment between the pixels of the end points, which are pixel centers.
+    fn pixel_segment(&self) -> crate::ivec2::LineSegment {
+        let half = glam::Vec2::splat(0.5);
+        crate::ivec2::LineSegment::new((self.start + half).floor().as_ivec2(), (self.end + half).floor().as_ivec2())
+        }
     
     
     pub fn as_ivec2(&self) -> crate::ivec2::LineSegment {
@@ -46,3 +126,125 @@ impl LineSegment {
     }
     }
 
+impl crate::SignedDistance for LineSegment {
+    /// Returns the distance to the line, which has no inside. The end points are pixel centers,
+    /// use `aa_outline_iter` to draw the line with a width.
+    fn signed_distance(&self, point: glam::Vec2) -> f32 {
+        let (start, end) = (self.start, self.end);
+        let half = glam::Vec2::splat(0.5);
+        crate::iters::sdf_iter::segment_distance(start + half, end + half, point)
+    }
+
+    fn distance_bounds(&self) -> crate::ivec2::Rectangle {
+        let (start, end) = (self.start, self.end);
+        let half = glam::Vec2::splat(0.5);
+        crate::ivec2::Rectangle::new(
+            (start.min(end) + half).floor().as_ivec2(),
+            (start.max(end) + half).ceil().as_ivec2(),
+        )
+    }
+}
+
+impl crate::Shape for LineSegment {
+    type Vector = glam::Vec2;
+    type Scalar = f32;
+
+    fn position(&self) -> glam::Vec2 {
+        self.start
+    }
+
+    /// Returns the midpoint of the segment.
+    fn center(&self) -> glam::Vec2 {
+        (self.start + self.end) / 2.0
+    }
+
+    /// Returns `true` if the pixel at `coord` is one of the pixels of `fill_iter`.
+    fn contains(&self, coord: glam::Vec2) -> bool {
+        let pixel = coord.floor().as_ivec2();
+        let clip = crate::ivec2::Rectangle::new(pixel, pixel + glam::IVec2::ONE);
+        crate::Shape::fill_iter_clipped(self, clip).next().is_some()
+    }
+
+    fn bounding_box(&self) -> crate::ivec2::Rectangle {
+        let segment = self.pixel_segment();
+        crate::ivec2::Rectangle::new(segment.start.min(segment.end), segment.start.max(segment.end) + glam::IVec2::ONE)
+    }
+
+    /// Returns the pixels of the line drawn with Bresenham's algorithm.
+    fn fill_iter(&self) -> impl Iterator<Item = glam::IVec2> {
+        crate::ivec2::LineIter::new_from_segment(self.pixel_segment(), crate::ivec2::LineDrawAlgo::Bresenham)
+    }
+
+    /// A line has no inside, its outline are the same pixels as `fill_iter`.
+    fn outline_iter(&self) -> impl Iterator<Item = glam::IVec2> {
+        crate::Shape::fill_iter(self)
+    }
+
+    fn fill_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = glam::IVec2> {
+        crate::ivec2::LineIter::new_from_segment_clipped(self.pixel_segment(), crate::ivec2::LineDrawAlgo::Bresenham, &clip)
+    }
+
+    fn outline_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = glam::IVec2> {
+        crate::Shape::fill_iter_clipped(self, clip)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Shape;
+    use glam::Vec2;
+
+    #[test]
+    fn test_queries() {
+        let segment = LineSegment::new(Vec2::new(2.0, 2.0), Vec2::new(10.0, 2.0));
+
+        assert_eq!(segment.midpoint(), Vec2::new(6.0, 2.0));
+        assert_eq!(segment.direction(), Vec2::X);
+        assert_eq!(segment.closest_point(Vec2::new(4.0, 7.0)), Vec2::new(4.0, 2.0));
+        assert_eq!(segment.distance(Vec2::new(13.0, 6.0)), 5.0);
+
+        let (before, after) = segment.split_at(0.25);
+        assert_eq!((before.start, before.end), (Vec2::new(2.0, 2.0), Vec2::new(4.0, 2.0)));
+        assert_eq!((after.start, after.end), (Vec2::new(4.0, 2.0), Vec2::new(10.0, 2.0)));
+
+        let crossing = LineSegment::new(Vec2::new(5.0, 0.0), Vec2::new(5.0, 4.0));
+        assert_eq!(segment.intersection(&crossing), Some(SegmentIntersection::Point(Vec2::new(5.0, 2.0))));
+        let overlapping = LineSegment::new(Vec2::new(8.0, 2.0), Vec2::new(12.0, 2.0));
+        assert_eq!(
+            segment.intersection(&overlapping),
+            Some(SegmentIntersection::Overlap(crate::vec2::LineSegment::new(Vec2::new(8.0, 2.0), Vec2::new(10.0, 2.0))))
+        );
+    }
+
+    #[test]
+    fn test_shape() {
+        let segment = LineSegment::new(Vec2::new(1.0, 1.0), Vec2::new(9.0, 4.0));
+        let pixels: Vec<glam::IVec2> = segment.fill_iter().collect();
+
+        assert_eq!(pixels.first(), Some(&glam::IVec2::new(1, 1)));
+        assert_eq!(pixels.last(), Some(&glam::IVec2::new(9, 4)));
+        assert!(segment.outline_iter().eq(pixels.iter().copied()));
+        assert!(pixels.iter().all(|p| segment.contains(p.as_vec2())));
+        assert!(!segment.contains(Vec2::new(9.0, 1.0)));
+
+        let bounding_box = Shape::bounding_box(&segment);
+        assert_eq!(bounding_box, crate::ivec2::Rectangle::new(glam::IVec2::new(1, 1), glam::IVec2::new(10, 5)));
+        assert_eq!(segment.center(), Vec2::new(5.0, 2.5));
+    }
+
+    #[test]
+    fn test_pixel_iter_clipped() {
+        let segment = LineSegment::new(Vec2::new(1.0, 1.0), Vec2::new(9.0, 4.0));
+        let clip = crate::ivec2::Rectangle::new(glam::IVec2::new(3, 0), glam::IVec2::new(7, 3));
+        let algo = LineDrawAlgo::DDA;
+        let inside = |p: &Vec2| {
+            let pixel = (*p + Vec2::splat(0.5)).floor().as_ivec2();
+            clip.columns().contains(&pixel.x) && clip.rows().contains(&pixel.y)
+        };
+
+        let expected: Vec<_> = segment.pixel_iter(algo).filter(inside).collect();
+        assert!(!expected.is_empty());
+        assert_eq!(segment.pixel_iter_clipped(algo, clip).collect::<Vec<_>>(), expected);
+    }
+}
@@ -11,6 +11,46 @@ pub use circle::*;
 mod line_segment;
 pub use line_segment::*;
 
+
+mod ellipse;
+pub use ellipse::*;
+
+
+mod polygon;
+pub use polygon::*;
+
+
+mod triangle;
+pub use triangle::*;
+
+
+mod polyline;
+pub use polyline::*;
+
+
+mod arc;
+pub use arc::*;
+
+
+mod sector;
+pub use sector::*;
+
+
+mod rounded_rectangle;
+pub use rounded_rectangle::*;
+
+
+mod capsule;
+pub use capsule::*;
+
+
+mod quadratic_bezier;
+pub use quadratic_bezier::*;
+
+
+mod cubic_bezier;
+pub use cubic_bezier::*;
+
  
 mod line_iter;
 pub use line_iter::*;
@@ -3,7 +3,10 @@ use std::ops::Range;
 
 use glam::Vec2;
 use glam::IVec2;
-use crate::iters::rect_iter::RectanglePixels;
+use crate::iters::dash_iter::{DashPattern, Dashed};
+use crate::iters::rect_iter::{
+    BorderAlignment, RectangleBorderPixels, RectangleBorderSpans, RectanglePerimeter, RectanglePixels, RectangleSpans,
+};
 
 #[derive(Debug, Copy, Clone, Default)]
 pub struct Rectangle {
@@ -127,6 +130,38 @@ impl Rectangle {
         self.tl.x < other.br.x && self.br.x > other.tl.x && self.tl.y < other.br.y && self.br.y > other.tl.y
     }
 
+    /// Returns the area covered by both rectangles, or `None` if they don't overlap.
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        Some(Self::new(self.tl.max(other.tl), self.br.min(other.br)))
+    }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rectangle) -> Rectangle {
+        Self::new(self.tl.min(other.tl), self.br.max(other.br))
+    }
+
+    /// Returns the parts of this rectangle not covered by `other`.
+    ///
+    /// The remainder is split into up to four non-overlapping rectangles: a band above and below
+    /// `other` spanning the full width, and the parts left and right of it in between.
+    pub fn subtract(&self, other: &Rectangle) -> Vec<Rectangle> {
+        let Some(overlap) = self.intersection(other) else {
+            return vec![*self];
+        };
+
+        let parts = [
+            Self::new(self.tl, Vec2::new(self.br.x, overlap.tl.y)),
+            Self::new(Vec2::new(self.tl.x, overlap.br.y), self.br),
+            Self::new(Vec2::new(self.tl.x, overlap.tl.y), overlap.bl()),
+            Self::new(overlap.tr(), Vec2::new(self.br.x, overlap.br.y)),
+        ];
+
+        parts.into_iter().filter(|part| !part.is_zero_sized()).collect()
+    }
+
     /// Returns the range of Y coordinates in this rectangle.
     pub fn rows(&self) -> Range<f32> {
         self.tl.y..self.br.y  
@@ -156,14 +191,67 @@ impl Rectangle {
 
     pub fn pixel_iter(&self, outline: bool) -> RectanglePixels {
         
-        let irect = crate::ivec2::Rectangle::new(self.tl.as_ivec2(), self.br.as_ivec2());
-        RectanglePixels::new(&irect, outline)
+        RectanglePixels::new(&self.as_rectangle_ivec2(), outline)
+        
+    }
+
+    /// Returns an iterator over the pixels of the shape that lie inside `clip`, the same pixels
+    /// `pixel_iter` yields inside of it.
+    pub fn pixel_iter_clipped(&self, outline: bool, clip: crate::ivec2::Rectangle) -> RectanglePixels {
+        RectanglePixels::new_clipped(&self.as_rectangle_ivec2(), outline, &clip)
         
     }
+
+    /// Returns an iterator over the outline of the rectangle in path order, clockwise from the
+    /// top left pixel.
+    pub fn perimeter_iter(&self) -> RectanglePerimeter {
+        RectanglePerimeter::new(&self.as_rectangle_ivec2())
+        }
+
+    /// Returns an iterator over the outline of the rectangle drawn with a dash pattern.
+    ///
+    /// The pattern runs clockwise from the top left pixel and continues around the corners.
+    pub fn dashed_outline_iter(&self, pattern: DashPattern) -> Dashed<RectanglePerimeter> {
+        Dashed::new(self.perimeter_iter(), pattern)
+    }
+
+    /// Returns an iterator over the rows of the rectangle, yielding each row with its range of
+    /// columns.
+    pub fn span_iter(&self) -> RectangleSpans {
+        RectangleSpans::new(&self.as_rectangle_ivec2())
+        }
+    /// Returns an iterator over the pixels of a border `thickness` pixels wide.
+    ///
+    /// `alignment` selects whether the border grows inward, outward or is centered on the edges.
+    /// Each pixel is yielded once.
+    pub fn border_pixel_iter(&self, thickness: u32, alignment: BorderAlignment) -> RectangleBorderPixels {
+        RectangleBorderPixels::new(&self.as_rectangle_ivec2(), thickness, alignment)
+        }
+
+    /// Returns the rows of a border `thickness` pixels wide as spans, see `border_pixel_iter`.
+    ///
+    /// Rows crossing the sides of the border yield two spans, one for each side.
+    pub fn border_span_iter(&self, thickness: u32, alignment: BorderAlignment) -> RectangleBorderSpans {
+        RectangleBorderSpans::new(&self.as_rectangle_ivec2(), thickness, alignment)
+        }
+    
+    pub fn as_rectangle_ivec2(&self) -> crate::ivec2::Rectangle {
+        crate::ivec2::Rectangle::new(self.tl.as_ivec2(), self.br.as_ivec2())
+    }
+    
+    
+    
+    pub fn as_rectangle_uvec2(&self) -> crate::uvec2::Rectangle {
+        crate::uvec2::Rectangle::new(self.tl.as_uvec2(), self.br.as_uvec2())
+    }
+    
 }
 
 
-impl crate::Shape<Vec2> for Rectangle {
+impl crate::Shape for Rectangle {
+    type Vector = Vec2;
+    type Scalar = f32;
+
     fn position(&self) -> Vec2 {
         self.tl()
     }
@@ -176,11 +264,39 @@ impl crate::Shape<Vec2> for Rectangle {
         self.contains(coord)
     }
 
-    fn pixel_iter(&self, outline: bool) -> impl Iterator<Item = IVec2> {
-        self.pixel_iter(outline)
+    fn bounding_box(&self) -> crate::ivec2::Rectangle {
+        self.as_rectangle_ivec2()
+        }
+
+    fn fill_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(false)
+    }
+
+    fn outline_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(true)
+    }
+
+    fn fill_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(false, clip)
+    }
+
+    fn outline_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(true, clip)
     }
 }
 
+impl crate::SignedDistance for Rectangle {
+    fn signed_distance(&self, point: glam::Vec2) -> f32 {
+        let (tl, br) = (self.tl, self.br);
+        let offset = (point - (tl + br) / 2.0).abs() - (br - tl) / 2.0;
+        offset.max(glam::Vec2::ZERO).length() + offset.max_element().min(0.0)
+    }
+
+    fn distance_bounds(&self) -> crate::ivec2::Rectangle {
+        crate::ivec2::Rectangle::new(self.tl.floor().as_ivec2(), self.br.ceil().as_ivec2())
+        }
+}
+
 
 
 #[cfg(test)]
@@ -201,4 +317,51 @@ mod test {
         assert_eq!(rect.tl, Vec2::new(1.0, 2.0));
         assert_eq!(rect.br, Vec2::new(3.0, 4.0));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_boolean_ops() {
+        let a = Rectangle::new(Vec2::new(0.0, 0.0), Vec2::new(6.0, 6.0));
+        let b = Rectangle::new(Vec2::new(2.0, 2.0), Vec2::new(4.0, 8.0));
+        let c = Rectangle::new(Vec2::new(6.0, 0.0), Vec2::new(8.0, 6.0));
+
+        assert_eq!(a.intersection(&b), Some(Rectangle::new(Vec2::new(2.0, 2.0), Vec2::new(4.0, 6.0))));
+        assert_eq!(a.intersection(&c), None);
+        assert_eq!(a.union(&b), Rectangle::new(Vec2::new(0.0, 0.0), Vec2::new(6.0, 8.0)));
+
+        assert_eq!(a.subtract(&c), vec![a]);
+        assert_eq!(
+            a.subtract(&b),
+            vec![
+                Rectangle::new(Vec2::new(0.0, 0.0), Vec2::new(6.0, 2.0)),
+                Rectangle::new(Vec2::new(0.0, 2.0), Vec2::new(2.0, 6.0)),
+                Rectangle::new(Vec2::new(4.0, 2.0), Vec2::new(6.0, 6.0)),
+            ]
+        );
+        assert!(b.subtract(&a).iter().all(|part| !part.overlaps(&a)));
+    }
+
+    #[test]
+    fn test_dashed_outline() {
+        let rect = Rectangle::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 3.0));
+        let outline: Vec<IVec2> = rect.perimeter_iter().collect();
+        assert_eq!(outline.len(), 10);
+        assert_eq!(outline[0], IVec2::new(0, 0));
+        assert_eq!(outline[4], IVec2::new(3, 1));
+
+        let dashed: Vec<IVec2> = rect.dashed_outline_iter(DashPattern::new(&[2, 1]).with_phase(1)).collect();
+        let expected: Vec<IVec2> = outline.into_iter().enumerate().filter(|(i, _)| i % 3 != 1).map(|(_, p)| p).collect();
+        assert_eq!(dashed, expected);
+    }
+
+    #[test]
+    fn test_border() {
+        let rect = Rectangle::new(Vec2::new(2.0, 2.0), Vec2::new(8.0, 6.0));
+        assert_eq!(rect.border_pixel_iter(2, BorderAlignment::Inside).count(), 24);
+        assert_eq!(rect.border_pixel_iter(2, BorderAlignment::Outside).count(), 80 - 24);
+        assert_eq!(rect.border_pixel_iter(2, BorderAlignment::Center).count(), 48 - 8);
+
+        let spans: Vec<(i32, Range<i32>)> = rect.border_span_iter(1, BorderAlignment::Outside).collect();
+        assert_eq!(spans[0], (1, 1..9));
+        assert_eq!(spans[1..3], [(2, 1..2), (2, 8..9)]);
+    }
+}
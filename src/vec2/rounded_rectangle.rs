@@ -0,0 +1,133 @@
+use glam::Vec2;
+use glam::IVec2;
+use crate::iters::rounded_rect_iter::{RoundedRectanglePixels, RoundedRectangleSpans};
+use super::rectangle::Rectangle;
+
+/// A rectangle with rounded corners.
+///
+/// Each corner has the pixels of a filled `Circle` with `radius`, the radius is limited to half
+/// the width and height of the rectangle.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct RoundedRectangle {
+    pub rect: Rectangle,
+    pub radius: f32,
+}
+
+impl RoundedRectangle {
+    pub const fn new(rect: Rectangle, radius: f32) -> Self {
+        Self { rect, radius }
+    }
+
+    /// Returns an iterator over the pixels of the rounded rectangle.
+    ///
+    /// The outline consists of the filled pixels next to a pixel that is not filled.
+    pub fn pixel_iter(&self, outline: bool) -> RoundedRectanglePixels {
+        RoundedRectanglePixels::new(&self.rect_ivec2(), self.radius_i32(), outline, None)
+    }
+
+    /// Returns an iterator over the pixels of the shape that lie inside `clip`, the same pixels
+    /// `pixel_iter` yields inside of it.
+    pub fn pixel_iter_clipped(&self, outline: bool, clip: crate::ivec2::Rectangle) -> RoundedRectanglePixels {
+        RoundedRectanglePixels::new(&self.rect_ivec2(), self.radius_i32(), outline, Some(clip))
+    }
+
+    /// Returns an iterator over the rows of the filled rounded rectangle, yielding each row with
+    /// its range of columns.
+    pub fn span_iter(&self) -> RoundedRectangleSpans {
+        RoundedRectangleSpans::new(&self.rect_ivec2(), self.radius_i32())
+    }
+
+    fn rect_ivec2(&self) -> crate::ivec2::Rectangle {
+        self.rect.as_rectangle_ivec2()
+        }
+
+    fn radius_i32(&self) -> i32 {
+        self.radius as i32
+    }
+    
+    pub fn as_rounded_rectangle_ivec2(&self) -> crate::ivec2::RoundedRectangle {
+        crate::ivec2::RoundedRectangle::new(self.rect.as_rectangle_ivec2(), self.radius as i32)
+    }
+    
+    
+    
+    pub fn as_rounded_rectangle_uvec2(&self) -> crate::uvec2::RoundedRectangle {
+        crate::uvec2::RoundedRectangle::new(self.rect.as_rectangle_uvec2(), self.radius as u32)
+    }
+    
+}
+
+impl crate::Shape for RoundedRectangle {
+    type Vector = Vec2;
+    type Scalar = f32;
+
+    fn position(&self) -> Vec2 {
+        self.rect.tl
+    }
+
+    fn center(&self) -> Vec2 {
+        crate::Shape::center(&self.rect)
+    }
+
+    /// Returns `true` if the pixel at `coord` is filled.
+    fn contains(&self, coord: Vec2) -> bool {
+        crate::iters::rounded_rect_iter::contains(&self.rect_ivec2(), self.radius_i32(), coord.floor().as_ivec2())
+    }
+
+    fn bounding_box(&self) -> crate::ivec2::Rectangle {
+        self.rect_ivec2()
+    }
+
+    fn fill_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(false)
+    }
+
+    fn outline_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(true)
+    }
+
+    fn fill_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(false, clip)
+    }
+
+    fn outline_iter_clipped(&self, clip: crate::ivec2::Rectangle) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter_clipped(true, clip)
+    }
+}
+
+impl crate::SignedDistance for RoundedRectangle {
+    fn signed_distance(&self, point: glam::Vec2) -> f32 {
+        let (tl, br) = (self.rect.tl, self.rect.br);
+        let half_size = (br - tl) / 2.0;
+        let radius = self.radius.clamp(0.0, half_size.min_element().max(0.0));
+        let offset = (point - (tl + br) / 2.0).abs() - half_size + glam::Vec2::splat(radius);
+        offset.max(glam::Vec2::ZERO).length() + offset.max_element().min(0.0) - radius
+    }
+
+    fn distance_bounds(&self) -> crate::ivec2::Rectangle {
+        crate::SignedDistance::distance_bounds(&self.rect)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Shape;
+
+    #[test]
+    fn test_contains() {
+        let rect = Rectangle::new(Vec2::new(0.0, 0.0), Vec2::new(10.0, 8.0));
+        let rounded = RoundedRectangle::new(rect, 3.0);
+
+        assert!(!rounded.contains(Vec2::new(0.0, 0.0)));
+        assert!(!rounded.contains(Vec2::new(9.0, 7.0)));
+        assert!(rounded.contains(Vec2::new(1.0, 1.0)));
+        assert!(rounded.contains(Vec2::new(0.0, 3.0)));
+        assert!(rounded.contains(Vec2::new(5.0, 0.0)));
+        assert!(!rounded.contains(Vec2::new(10.0, 3.0)));
+
+        let spans: Vec<IVec2> = rounded.span_iter().flat_map(|(y, columns)| columns.map(move |x| IVec2::new(x, y))).collect();
+        assert_eq!(spans, rounded.pixel_iter(false).collect::<Vec<IVec2>>());
+    }
+}
@@ -0,0 +1,125 @@
+use glam::Vec2;
+use glam::IVec2;
+use crate::iters::arc_iter::{AngleRange, SectorPixels};
+use super::circle::Circle;
+
+/// A filled slice of a circle between two angles.
+///
+/// Angles are in radians, `0.0` points along the positive x axis and angles increase clockwise
+/// on screen. The sector runs clockwise from `start_angle` to `end_angle`, sectors sharing an
+/// angle fill the circle without gaps or overlaps.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Sector {
+    pub circle: Circle,
+    pub start_angle: f32,
+    pub end_angle: f32,
+}
+
+impl Sector {
+    pub const fn new(circle: Circle, start_angle: f32, end_angle: f32) -> Self {
+        Self {
+            circle,
+            start_angle,
+            end_angle,
+        }
+    }
+
+    /// Returns an iterator over the pixels of the sector.
+    pub fn pixel_iter(&self, outline: bool) -> SectorPixels {
+        SectorPixels::new(&self.circle_ivec2(), self.angle_range(), outline)
+    }
+
+    fn angle_range(&self) -> AngleRange {
+        AngleRange::new(self.start_angle, self.end_angle)
+    }
+
+    fn circle_ivec2(&self) -> crate::ivec2::Circle {
+        self.circle.as_circle_ivec2()
+        }
+    
+    pub fn as_sector_ivec2(&self) -> crate::ivec2::Sector {
+        crate::ivec2::Sector::new(self.circle.as_circle_ivec2(), self.start_angle, self.end_angle)
+    }
+    
+    
+    
+    pub fn as_sector_uvec2(&self) -> crate::uvec2::Sector {
+        crate::uvec2::Sector::new(self.circle.as_circle_uvec2(), self.start_angle, self.end_angle)
+    }
+    
+}
+
+impl crate::Shape for Sector {
+    type Vector = Vec2;
+    type Scalar = f32;
+
+    fn position(&self) -> Vec2 {
+        crate::Shape::position(&self.circle)
+    }
+
+    /// Returns the center of the circle the sector is cut from.
+    fn center(&self) -> Vec2 {
+        crate::Shape::center(&self.circle)
+    }
+
+    fn contains(&self, coord: Vec2) -> bool {
+        crate::iters::arc_iter::sector_contains(&self.circle_ivec2(), self.angle_range(), coord.floor().as_ivec2())
+    }
+
+    fn bounding_box(&self) -> crate::ivec2::Rectangle {
+        crate::Shape::bounding_box(&self.circle)
+    }
+
+    fn fill_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(false)
+    }
+
+    fn outline_iter(&self) -> impl Iterator<Item = IVec2> {
+        self.pixel_iter(true)
+    }
+}
+
+impl crate::SignedDistance for Sector {
+    fn signed_distance(&self, point: glam::Vec2) -> f32 {
+        crate::iters::arc_iter::sector_distance(self.circle.pos, self.circle.radius, self.angle_range(), point)
+    }
+
+    fn distance_bounds(&self) -> crate::ivec2::Rectangle {
+        crate::SignedDistance::distance_bounds(&self.circle)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Shape;
+
+    #[test]
+    fn test_contains() {
+        let circle = Circle::new(Vec2::new(10.0, 10.0), 5.0);
+        let sector = Sector::new(circle, 0.0, std::f32::consts::FRAC_PI_2);
+
+        assert!(sector.contains(Vec2::new(10.0, 10.0)));
+        assert!(sector.contains(Vec2::new(12.0, 12.0)));
+        assert!(!sector.contains(Vec2::new(9.0, 12.0)));
+        assert!(!sector.contains(Vec2::new(12.0, 9.0)));
+        assert!(!sector.contains(Vec2::new(14.0, 14.0)));
+        assert_eq!(sector.pixel_iter(false).count() * 4, circle.pixel_iter(false).count());
+    }
+
+    #[test]
+    fn test_signed_distance() {
+        use crate::SignedDistance;
+
+        let circle = Circle::new(Vec2::new(10.0, 10.0), 6.0);
+        let sector = Sector::new(circle, 0.0, std::f32::consts::FRAC_PI_2);
+
+        assert!(sector.signed_distance(glam::Vec2::new(12.0, 12.0)) < 0.0);
+        assert!(sector.signed_distance(glam::Vec2::new(8.0, 12.0)) > 0.0);
+        assert!(sector.signed_distance(glam::Vec2::new(12.0, 8.0)) > 0.0);
+
+        let area: f32 = sector.aa_fill_iter().map(|(_, coverage)| coverage).sum();
+        assert!((area - std::f32::consts::PI * 9.0).abs() < 1.0, "{}", area);
+    }
+}